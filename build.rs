@@ -0,0 +1,23 @@
+fn main() {
+    #[cfg(feature = "ffi")]
+    generate_header();
+}
+
+/// Generate C header for the `ffi` module into `$OUT_DIR/hdpath.h`. Only `src/ffi.rs` is parsed, so types from the
+/// rest of the crate never end up in the header. The build never writes to the source tree, and a test in `ffi`
+/// compares the generated header with the committed `include/hdpath.h`
+#[cfg(feature = "ffi")]
+fn generate_header() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("Invalid cbindgen.toml");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{}/src/ffi.rs", crate_dir))
+        .generate()
+        .expect("Unable to generate C header")
+        .write_to_file(format!("{}/hdpath.h", out_dir));
+}
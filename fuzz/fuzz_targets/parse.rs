@@ -0,0 +1,21 @@
+#![no_main]
+
+use hdpath::{AccountHDPath, CustomHDPath, HDPath, ParseOptions, StandardHDPath};
+use libfuzzer_sys::fuzz_target;
+use std::str::FromStr;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(value) = std::str::from_utf8(data) {
+        for options in [ParseOptions::default(), ParseOptions::strict(), ParseOptions::lenient()] {
+            if let Ok(hd_path) = CustomHDPath::parse_with(value, &options) {
+                // whatever is parsed must be displayed in a canonical form, which is parsed back to the same path
+                let canonical = hd_path.to_string();
+                assert_eq!(Ok(hd_path.clone()), CustomHDPath::parse_with(canonical.as_str(), &ParseOptions::strict()));
+                assert_eq!(hd_path.len() as usize, hd_path.0.len());
+            }
+        }
+        let _ = StandardHDPath::from_str(value);
+        let _ = AccountHDPath::from_str(value);
+    }
+    let _ = StandardHDPath::from_bytes(data);
+});
@@ -0,0 +1,125 @@
+#ifndef HDPATH_H
+#define HDPATH_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result code of a function call
+typedef enum HDPathResult {
+  HD_PATH_RESULT_OK = 0,
+  HD_PATH_RESULT_NULL_POINTER = -1,
+  HD_PATH_RESULT_INVALID_UTF8 = -2,
+  HD_PATH_RESULT_BUFFER_TOO_SMALL = -3,
+  HD_PATH_RESULT_OUT_OF_BOUNDS = -4,
+  HD_PATH_RESULT_HIGH_BIT_IS_SET = -10,
+  HD_PATH_RESULT_INVALID_LENGTH = -11,
+  HD_PATH_RESULT_INVALID_PURPOSE = -12,
+  HD_PATH_RESULT_INVALID_STRUCTURE = -13,
+  HD_PATH_RESULT_INVALID_FORMAT = -14,
+  HD_PATH_RESULT_KEY_DERIVATION = -15,
+  HD_PATH_RESULT_INVALID_MNEMONIC = -16,
+} HDPathResult;
+
+// Kind of a path, see `hdpath::classify`
+typedef enum HDPathKind {
+  HD_PATH_KIND_UNKNOWN = 0,
+  HD_PATH_KIND_STANDARD = 1,
+  HD_PATH_KIND_ACCOUNT = 2,
+  HD_PATH_KIND_MULTISIG = 3,
+  HD_PATH_KIND_MULTISIG_ACCOUNT = 4,
+  HD_PATH_KIND_LEDGER_LIVE = 5,
+  HD_PATH_KIND_SHORT = 6,
+  HD_PATH_KIND_ELECTRUM = 7,
+} HDPathKind;
+
+// Opaque handle to a parsed HD Path
+typedef struct HDPathHandle HDPathHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Parse a NUL-terminated string, like `m/44'/0'/0'/0/0`, into a new handle written to `out`.
+//
+// # Safety
+// `input` must be a valid NUL-terminated string and `out` a valid pointer. The handle must be released
+// with `hdpath_free`.
+enum HDPathResult hdpath_parse(const char *input,
+                               struct HDPathHandle **out);
+
+// Decode the `to_bytes` encoding (number of elements following by 4-byte BE values) into a new handle written to `out`.
+//
+// # Safety
+// `data` must point to `len` readable bytes and `out` must be a valid pointer. The handle must be released
+// with `hdpath_free`.
+enum HDPathResult hdpath_from_bytes(const uint8_t *data,
+                                    uintptr_t len,
+                                    struct HDPathHandle **out);
+
+// Release the handle. Does nothing if `path` is NULL.
+//
+// # Safety
+// `path` must be a handle created by this library and not released before.
+void hdpath_free(struct HDPathHandle *path);
+
+// Write the number of elements of the path to `out_len`.
+//
+// # Safety
+// `path` must be a valid handle and `out_len` a valid pointer.
+enum HDPathResult hdpath_len(const struct HDPathHandle *path, uint8_t *out_len);
+
+// Write the element at `pos` to `out_value` as a raw value, i.e. with `0x80000000` bit set for a hardened value.
+// Returns `HD_PATH_RESULT_OUT_OF_BOUNDS` if there is no such element.
+//
+// # Safety
+// `path` must be a valid handle and `out_value` a valid pointer.
+enum HDPathResult hdpath_get(const struct HDPathHandle *path,
+                             uint8_t pos,
+                             uint32_t *out_value);
+
+// Write the path as a NUL-terminated string, like `m/44'/0'/0'/0/0`, into `buf` of `buf_len` bytes.
+// The size of the string including NUL is written to `out_len`, and if it's larger than `buf_len` the function
+// returns `HD_PATH_RESULT_BUFFER_TOO_SMALL`.
+//
+// # Safety
+// `path` must be a valid handle, `buf` must point to `buf_len` writable bytes and `out_len` must be a valid pointer.
+enum HDPathResult hdpath_format(const struct HDPathHandle *path,
+                                char *buf,
+                                uintptr_t buf_len,
+                                uintptr_t *out_len);
+
+// Write the `to_bytes` encoding of the path into `buf` of `buf_len` bytes. The size of the encoding is written
+// to `out_len`, and if it's larger than `buf_len` the function returns `HD_PATH_RESULT_BUFFER_TOO_SMALL`.
+//
+// # Safety
+// `path` must be a valid handle, `buf` must point to `buf_len` writable bytes and `out_len` must be a valid pointer.
+enum HDPathResult hdpath_to_bytes(const struct HDPathHandle *path,
+                                  uint8_t *buf,
+                                  uintptr_t buf_len,
+                                  uintptr_t *out_len);
+
+// Check that the NUL-terminated string is a valid standard path `m/purpose'/coin_type'/account'/change/index`.
+// Returns `HD_PATH_RESULT_OK` if it is, or the error code otherwise.
+//
+// # Safety
+// `input` must be a valid NUL-terminated string.
+enum HDPathResult hdpath_validate_standard(const char *input);
+
+// Write the kind of the path (see `HDPathKind`) to `out_kind`.
+//
+// # Safety
+// `path` must be a valid handle and `out_kind` a valid pointer.
+enum HDPathResult hdpath_classify(const struct HDPathHandle *path, enum HDPathKind *out_kind);
+
+// Static NUL-terminated description of the result code, or `Unknown error` for an unknown code. Must not be released.
+const char *hdpath_error_message(int code);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* HDPATH_H */
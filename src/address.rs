@@ -0,0 +1,173 @@
+use crate::{AccountKeys, Error, ExtendedPrivateKey, Purpose, ScriptType, StandardHDPath};
+use bitcoin::{Address, AddressType, CompressedPublicKey, Network};
+use bitcoin::secp256k1::{PublicKey, Secp256k1, Signing, Verification};
+use std::ops::Range;
+
+impl ScriptType {
+
+    /// Type of address for the script type
+    pub fn address_type(&self) -> AddressType {
+        match self {
+            ScriptType::P2pkh => AddressType::P2pkh,
+            ScriptType::P2shP2wpkh | ScriptType::P2shP2wsh => AddressType::P2sh,
+            ScriptType::P2wpkh => AddressType::P2wpkh,
+            ScriptType::P2tr => AddressType::P2tr,
+            ScriptType::P2wsh => AddressType::P2wsh,
+        }
+    }
+}
+
+impl Purpose {
+
+    /// Type of address used for the purpose:
+    /// - BIP-44 (`44'`) - P2PKH
+    /// - BIP-49 (`49'`) - P2SH, which wraps P2WPKH
+    /// - BIP-84 (`84'`) - P2WPKH
+    /// - BIP-86 (`86'`) - P2TR, with key-path spending only
+    ///
+    /// Returns `None` for other purposes
+    pub fn address_type(&self) -> Option<AddressType> {
+        ScriptType::from_purpose(self).map(|s| s.address_type())
+    }
+
+    /// Encode the public key as an address of the type used for the purpose (see `address_type()`).
+    ///
+    /// Returns `Error::InvalidPurpose` if the purpose doesn't define an address type.
+    pub fn address_for_key<C: Verification>(&self, secp: &Secp256k1<C>, key: &PublicKey, network: Network) -> Result<Address, Error> {
+        let compressed = CompressedPublicKey(*key);
+        match self.address_type() {
+            Some(AddressType::P2pkh) => Ok(Address::p2pkh(compressed, network)),
+            Some(AddressType::P2sh) => Ok(Address::p2shwpkh(&compressed, network)),
+            Some(AddressType::P2wpkh) => Ok(Address::p2wpkh(&compressed, network)),
+            Some(AddressType::P2tr) => Ok(Address::p2tr(secp, key.x_only_public_key().0, None, network)),
+            _ => Err(Error::InvalidPurpose(self.as_value().as_number()))
+        }
+    }
+}
+
+impl StandardHDPath {
+
+    /// Derive the key for the current path from the master (root) private key and encode it as an address.
+    /// The type of the address is defined by the purpose.
+    ///
+    /// ```
+    /// use hdpath::{Seed, StandardHDPath};
+    /// use bitcoin::secp256k1::Secp256k1;
+    /// use bitcoin::Network;
+    /// # use std::str::FromStr;
+    ///
+    /// let secp = Secp256k1::new();
+    /// let master = Seed::from_bytes(&[0u8; 32]).unwrap().to_master(Network::Bitcoin.into()).unwrap();
+    /// let hd_path = StandardHDPath::from_str("m/84'/0'/0'/0/0").unwrap();
+    /// // gives a P2WPKH address, i.e. bc1q...
+    /// let address = hd_path.address(&secp, &master, Network::Bitcoin).unwrap();
+    /// ```
+    pub fn address<C: Signing + Verification>(&self, secp: &Secp256k1<C>, master: &ExtendedPrivateKey, network: Network) -> Result<Address, Error> {
+        if self.purpose().address_type().is_none() {
+            return Err(Error::InvalidPurpose(self.purpose().as_value().as_number()))
+        }
+        let key = master.derive(secp, self)?;
+        self.purpose().address_for_key(secp, &key.public_key(secp), network)
+    }
+}
+
+impl AccountKeys {
+
+    /// Derive addresses on the `change` chain with indexes in the `range`, using `derive_range`.
+    /// The type of the address is defined by the purpose of the account.
+    pub fn derive_addresses<C: Verification>(&self, secp: &Secp256k1<C>, change: u32, range: Range<u32>, network: Network) -> Result<Vec<(StandardHDPath, Address)>, Error> {
+        let purpose = self.path().purpose();
+        if purpose.address_type().is_none() {
+            return Err(Error::InvalidPurpose(purpose.as_value().as_number()))
+        }
+        self.derive_range(secp, change, range)?
+            .into_iter()
+            .map(|(path, key)| purpose.address_for_key(secp, &key, network).map(|address| (path, address)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountHDPath, Seed};
+    use std::str::FromStr;
+
+    // seed for "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", used by BIP-49, BIP-84 and BIP-86 test vectors
+    const SEED: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+
+    fn master(network: Network) -> ExtendedPrivateKey {
+        let seed: Vec<u8> = (0..SEED.len()).step_by(2)
+            .map(|i| u8::from_str_radix(&SEED[i..i + 2], 16).unwrap())
+            .collect();
+        Seed::from_bytes(&seed).unwrap().to_master(network.into()).unwrap()
+    }
+
+    fn address(path: &str, network: Network) -> String {
+        let secp = Secp256k1::new();
+        StandardHDPath::from_str(path).unwrap()
+            .address(&secp, &master(network), network).unwrap()
+            .to_string()
+    }
+
+    #[test]
+    pub fn address_type() {
+        assert_eq!(Some(AddressType::P2pkh), Purpose::Pubkey.address_type());
+        assert_eq!(Some(AddressType::P2sh), Purpose::ScriptHash.address_type());
+        assert_eq!(Some(AddressType::P2wpkh), Purpose::Witness.address_type());
+        assert_eq!(Some(AddressType::P2tr), Purpose::Taproot.address_type());
+        assert_eq!(Some(AddressType::P2wpkh), Purpose::Custom(84).address_type());
+        assert_eq!(None, Purpose::Custom(48).address_type());
+        assert_eq!(None, Purpose::None.address_type());
+    }
+
+    #[test]
+    pub fn script_address_type() {
+        assert_eq!(AddressType::P2sh, ScriptType::P2shP2wsh.address_type());
+        assert_eq!(AddressType::P2wsh, ScriptType::P2wsh.address_type());
+    }
+
+    #[test]
+    pub fn bip44_address() {
+        assert_eq!("1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA", address("m/44'/0'/0'/0/0", Network::Bitcoin));
+    }
+
+    #[test]
+    pub fn bip49_address() {
+        assert_eq!("2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2", address("m/49'/1'/0'/0/0", Network::Testnet));
+    }
+
+    #[test]
+    pub fn bip84_address() {
+        assert_eq!("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu", address("m/84'/0'/0'/0/0", Network::Bitcoin));
+        assert_eq!("bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g", address("m/84'/0'/0'/0/1", Network::Bitcoin));
+        assert_eq!("bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el", address("m/84'/0'/0'/1/0", Network::Bitcoin));
+    }
+
+    #[test]
+    pub fn bip86_address() {
+        assert_eq!("bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr", address("m/86'/0'/0'/0/0", Network::Bitcoin));
+        assert_eq!("bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh", address("m/86'/0'/0'/0/1", Network::Bitcoin));
+        assert_eq!("bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7", address("m/86'/0'/0'/1/0", Network::Bitcoin));
+    }
+
+    #[test]
+    pub fn err_for_unknown_purpose() {
+        let secp = Secp256k1::new();
+        let act = StandardHDPath::from_str("m/101'/0'/0'/0/0").unwrap()
+            .address(&secp, &master(Network::Bitcoin), Network::Bitcoin);
+        assert_eq!(Err(Error::InvalidPurpose(101)), act);
+    }
+
+    #[test]
+    pub fn derive_account_addresses() {
+        let secp = Secp256k1::new();
+        let account = AccountKeys::from_master(&secp, &master(Network::Bitcoin), &AccountHDPath::from_str("m/84'/0'/0'").unwrap()).unwrap();
+        let act = account.derive_addresses(&secp, 0, 0..2, Network::Bitcoin).unwrap();
+        assert_eq!(2, act.len());
+        assert_eq!("m/84'/0'/0'/0/0", act[0].0.to_string());
+        assert_eq!("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu", act[0].1.to_string());
+        assert_eq!("m/84'/0'/0'/0/1", act[1].0.to_string());
+        assert_eq!("bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g", act[1].1.to_string());
+    }
+}
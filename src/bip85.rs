@@ -0,0 +1,496 @@
+use crate::{CustomHDPath, Error, PathValue};
+use crate::traits::HDPath;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+#[cfg(feature = "with-bitcoin")]
+use crate::ExtendedPrivateKey;
+#[cfg(feature = "with-bitcoin")]
+use bitcoin::{
+    bip32::{ChainCode, ChildNumber, Fingerprint, Xpriv},
+    hashes::{hmac, sha512, Hash, HashEngine},
+    secp256k1::{Secp256k1, SecretKey, Signing},
+    NetworkKind, PrivateKey,
+};
+#[cfg(feature = "with-bitcoin")]
+use zeroize::Zeroizing;
+
+/// Purpose of BIP-85 paths
+pub const BIP85_PURPOSE: u32 = 83696968;
+
+/// Language of a BIP-39 mnemonic produced by [BIP-85](https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki#bip39)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum MnemonicLanguage {
+    English,
+    Japanese,
+    Korean,
+    Spanish,
+    ChineseSimplified,
+    ChineseTraditional,
+    French,
+    Italian,
+    Czech,
+    Portuguese,
+}
+
+const LANGUAGES: [MnemonicLanguage; 10] = [
+    MnemonicLanguage::English,
+    MnemonicLanguage::Japanese,
+    MnemonicLanguage::Korean,
+    MnemonicLanguage::Spanish,
+    MnemonicLanguage::ChineseSimplified,
+    MnemonicLanguage::ChineseTraditional,
+    MnemonicLanguage::French,
+    MnemonicLanguage::Italian,
+    MnemonicLanguage::Czech,
+    MnemonicLanguage::Portuguese,
+];
+
+impl MnemonicLanguage {
+    /// Code of the language in the path
+    pub fn code(&self) -> u32 {
+        LANGUAGES.iter().position(|l| l == self).unwrap() as u32
+    }
+
+    pub fn from_code(code: u32) -> Option<MnemonicLanguage> {
+        LANGUAGES.get(code as usize).copied()
+    }
+}
+
+/// Application of the BIP-85 entropy, with its parameters
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Application {
+    /// BIP-39 mnemonic, `39'/{language}'/{words}'`, where words is 12, 18 or 24
+    Mnemonic { language: MnemonicLanguage, words: u32 },
+    /// HD-Seed WIF, `2'`
+    Wif,
+    /// XPRV, `32'`
+    Xprv,
+    /// Hex, `128169'/{num_bytes}'`, where num_bytes is 16 to 64
+    Hex { num_bytes: u32 },
+    /// Base64 password, `707764'/{length}'`, where length is 20 to 86
+    Password { length: u32 },
+}
+
+impl Application {
+
+    /// Check the parameters of the application
+    pub fn validate(&self) -> Result<(), Error> {
+        let valid = match self {
+            Application::Mnemonic { words, .. } => *words == 12 || *words == 18 || *words == 24,
+            Application::Wif | Application::Xprv => true,
+            Application::Hex { num_bytes } => (16..=64).contains(num_bytes),
+            Application::Password { length } => (20..=86).contains(length),
+        };
+        if valid { Ok(()) } else { Err(Error::InvalidStructure) }
+    }
+
+    /// Path elements of the application, without purpose and index
+    fn values(&self) -> Vec<u32> {
+        match self {
+            Application::Mnemonic { language, words } => vec![39, language.code(), *words],
+            Application::Wif => vec![2],
+            Application::Xprv => vec![32],
+            Application::Hex { num_bytes } => vec![128169, *num_bytes],
+            Application::Password { length } => vec![707764, *length],
+        }
+    }
+}
+
+/// HD Path of [BIP-85](https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki) deterministic entropy,
+/// `m/83696968'/{app_no}'/{params...}'/{index}'`, where all elements are hardened.
+///
+/// # Usage
+/// ```
+/// use hdpath::{Application, Bip85Path, MnemonicLanguage};
+/// # use std::str::FromStr;
+///
+/// let hd_path = Bip85Path::new(Application::Mnemonic { language: MnemonicLanguage::English, words: 12 }, 0).unwrap();
+/// assert_eq!("m/83696968'/39'/0'/12'/0'", hd_path.to_string());
+///
+/// let hd_path = Bip85Path::from_str("m/83696968'/128169'/64'/0'").unwrap();
+/// assert_eq!(Application::Hex { num_bytes: 64 }, hd_path.application());
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Bip85Path {
+    application: Application,
+    index: u32,
+}
+
+impl Bip85Path {
+
+    pub fn new(application: Application, index: u32) -> Result<Bip85Path, Error> {
+        application.validate()?;
+        if !PathValue::is_ok(index) {
+            return Err(Error::HighBitIsSet)
+        }
+        Ok(Bip85Path { application, index })
+    }
+
+    pub fn application(&self) -> Application {
+        self.application
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    fn values(&self) -> Vec<u32> {
+        let mut values = vec![BIP85_PURPOSE];
+        values.extend(self.application.values());
+        values.push(self.index);
+        values
+    }
+}
+
+impl HDPath for Bip85Path {
+    fn len(&self) -> u8 {
+        self.values().len() as u8
+    }
+
+    fn get(&self, pos: u8) -> Option<PathValue> {
+        self.values().get(pos as usize).map(|v| PathValue::Hardened(*v))
+    }
+}
+
+impl TryFrom<CustomHDPath> for Bip85Path {
+    type Error = Error;
+
+    fn try_from(value: CustomHDPath) -> Result<Self, Self::Error> {
+        let mut values = Vec::with_capacity(value.0.len());
+        for v in value.0.iter() {
+            match v {
+                PathValue::Hardened(n) => values.push(*n),
+                PathValue::Normal(_) => return Err(Error::InvalidStructure)
+            }
+        }
+        match values.first() {
+            Some(&BIP85_PURPOSE) => {}
+            Some(other) => return Err(Error::InvalidPurpose(*other)),
+            None => return Err(Error::InvalidLength(0))
+        }
+        let (application, expected_len) = match values.get(1) {
+            Some(39) => {
+                let language = values.get(2)
+                    .and_then(|code| MnemonicLanguage::from_code(*code))
+                    .ok_or(Error::InvalidStructure)?;
+                let words = *values.get(3).ok_or(Error::InvalidLength(values.len()))?;
+                (Application::Mnemonic { language, words }, 5)
+            }
+            Some(2) => (Application::Wif, 3),
+            Some(32) => (Application::Xprv, 3),
+            Some(128169) => {
+                let num_bytes = *values.get(2).ok_or(Error::InvalidLength(values.len()))?;
+                (Application::Hex { num_bytes }, 4)
+            }
+            Some(707764) => {
+                let length = *values.get(2).ok_or(Error::InvalidLength(values.len()))?;
+                (Application::Password { length }, 4)
+            }
+            Some(_) => return Err(Error::InvalidStructure),
+            None => return Err(Error::InvalidLength(values.len()))
+        };
+        if values.len() != expected_len {
+            return Err(Error::InvalidLength(values.len()))
+        }
+        Bip85Path::new(application, values[expected_len - 1])
+    }
+}
+
+impl FromStr for Bip85Path {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = CustomHDPath::from_str(s)?;
+        Bip85Path::try_from(value)
+    }
+}
+
+impl fmt::Display for Bip85Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for value in self.values() {
+            write!(f, "/{}'", value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "with-bitcoin")]
+impl Bip85Path {
+
+    /// Derive the 64 bytes of the BIP-85 entropy from the master key
+    pub fn entropy<C: Signing>(&self, secp: &Secp256k1<C>, master: &ExtendedPrivateKey) -> Result<Zeroizing<[u8; 64]>, Error> {
+        let key = master.derive(secp, self)?;
+        let mut engine = hmac::HmacEngine::<sha512::Hash>::new(b"bip-entropy-from-k");
+        engine.input(&key.expose_secret().private_key.secret_bytes());
+        let hash = hmac::Hmac::<sha512::Hash>::from_engine(engine);
+        Ok(Zeroizing::new(hash.to_byte_array()))
+    }
+
+    fn expect(&self, valid: bool) -> Result<(), Error> {
+        if valid { Ok(()) } else { Err(Error::InvalidStructure) }
+    }
+
+    /// Derive hex string for `Application::Hex`
+    pub fn to_hex<C: Signing>(&self, secp: &Secp256k1<C>, master: &ExtendedPrivateKey) -> Result<Zeroizing<String>, Error> {
+        let num_bytes = match self.application {
+            Application::Hex { num_bytes } => num_bytes as usize,
+            _ => return Err(Error::InvalidStructure)
+        };
+        let entropy = self.entropy(secp, master)?;
+        let mut result = String::with_capacity(num_bytes * 2);
+        for b in entropy[..num_bytes].iter() {
+            result.push(char::from_digit((b >> 4) as u32, 16).unwrap());
+            result.push(char::from_digit((b & 0x0f) as u32, 16).unwrap());
+        }
+        Ok(Zeroizing::new(result))
+    }
+
+    /// Derive private key in WIF format for `Application::Wif`
+    pub fn to_wif<C: Signing>(&self, secp: &Secp256k1<C>, master: &ExtendedPrivateKey) -> Result<Zeroizing<String>, Error> {
+        self.expect(self.application == Application::Wif)?;
+        let entropy = self.entropy(secp, master)?;
+        let key = SecretKey::from_slice(&entropy[..32]).map_err(|_| Error::KeyDerivation)?;
+        Ok(Zeroizing::new(PrivateKey::new(key, NetworkKind::Main).to_wif()))
+    }
+
+    /// Derive master key for `Application::Xprv`
+    pub fn to_xprv<C: Signing>(&self, secp: &Secp256k1<C>, master: &ExtendedPrivateKey) -> Result<ExtendedPrivateKey, Error> {
+        self.expect(self.application == Application::Xprv)?;
+        let entropy = self.entropy(secp, master)?;
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&entropy[..32]);
+        let private_key = SecretKey::from_slice(&entropy[32..]).map_err(|_| Error::KeyDerivation)?;
+        Ok(ExtendedPrivateKey::new(Xpriv {
+            network: NetworkKind::Main,
+            depth: 0,
+            parent_fingerprint: Fingerprint::default(),
+            child_number: ChildNumber::Normal { index: 0 },
+            private_key,
+            chain_code: ChainCode::from(chain_code),
+        }))
+    }
+
+    /// Derive Base64 password for `Application::Password`
+    pub fn to_password<C: Signing>(&self, secp: &Secp256k1<C>, master: &ExtendedPrivateKey) -> Result<Zeroizing<String>, Error> {
+        let length = match self.application {
+            Application::Password { length } => length as usize,
+            _ => return Err(Error::InvalidStructure)
+        };
+        let entropy = self.entropy(secp, master)?;
+        let mut result = base64(&entropy[..]);
+        result.truncate(length);
+        Ok(result)
+    }
+
+    /// Derive the mnemonic phrase for `Application::Mnemonic`
+    #[cfg(feature = "bip39")]
+    pub fn to_mnemonic<C: Signing>(&self, secp: &Secp256k1<C>, master: &ExtendedPrivateKey) -> Result<Zeroizing<String>, Error> {
+        let (language, words) = match self.application {
+            Application::Mnemonic { language, words } => (language, words as usize),
+            _ => return Err(Error::InvalidStructure)
+        };
+        let language = match language {
+            MnemonicLanguage::English => bip39::Language::English,
+            MnemonicLanguage::Japanese => bip39::Language::Japanese,
+            MnemonicLanguage::Korean => bip39::Language::Korean,
+            MnemonicLanguage::Spanish => bip39::Language::Spanish,
+            MnemonicLanguage::ChineseSimplified => bip39::Language::SimplifiedChinese,
+            MnemonicLanguage::ChineseTraditional => bip39::Language::TraditionalChinese,
+            MnemonicLanguage::French => bip39::Language::French,
+            MnemonicLanguage::Italian => bip39::Language::Italian,
+            MnemonicLanguage::Czech => bip39::Language::Czech,
+            MnemonicLanguage::Portuguese => bip39::Language::Portuguese,
+        };
+        let entropy = self.entropy(secp, master)?;
+        let mnemonic = bip39::Mnemonic::from_entropy_in(language, &entropy[..words * 4 / 3])
+            .map_err(|_| Error::InvalidMnemonic)?;
+        Ok(Zeroizing::new(mnemonic.to_string()))
+    }
+}
+
+/// Standard Base64 with padding
+#[cfg(feature = "with-bitcoin")]
+fn base64(value: &[u8]) -> Zeroizing<String> {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut result = Zeroizing::new(String::with_capacity(value.len().div_ceil(3) * 4));
+    for chunk in value.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn create_paths() {
+        let cases = [
+            (Application::Mnemonic { language: MnemonicLanguage::English, words: 12 }, 0, "m/83696968'/39'/0'/12'/0'"),
+            (Application::Mnemonic { language: MnemonicLanguage::Czech, words: 24 }, 3, "m/83696968'/39'/8'/24'/3'"),
+            (Application::Wif, 0, "m/83696968'/2'/0'"),
+            (Application::Xprv, 1, "m/83696968'/32'/1'"),
+            (Application::Hex { num_bytes: 64 }, 0, "m/83696968'/128169'/64'/0'"),
+            (Application::Password { length: 21 }, 0, "m/83696968'/707764'/21'/0'"),
+        ];
+        for (application, index, expected) in cases.iter() {
+            let hd_path = Bip85Path::new(*application, *index).unwrap();
+            assert_eq!(*expected, hd_path.to_string());
+            assert_eq!(hd_path, Bip85Path::from_str(expected).unwrap());
+            assert_eq!(*expected, hd_path.as_custom().to_string());
+        }
+    }
+
+    #[test]
+    pub fn validate_ranges() {
+        for words in [12, 18, 24].iter() {
+            assert!(Application::Mnemonic { language: MnemonicLanguage::English, words: *words }.validate().is_ok());
+        }
+        assert!(Application::Mnemonic { language: MnemonicLanguage::English, words: 15 }.validate().is_err());
+        assert!(Application::Hex { num_bytes: 15 }.validate().is_err());
+        assert!(Application::Hex { num_bytes: 16 }.validate().is_ok());
+        assert!(Application::Hex { num_bytes: 65 }.validate().is_err());
+        assert!(Application::Password { length: 19 }.validate().is_err());
+        assert!(Application::Password { length: 86 }.validate().is_ok());
+        assert!(Application::Password { length: 87 }.validate().is_err());
+        assert_eq!(Err(Error::HighBitIsSet), Bip85Path::new(Application::Wif, 0x80000000));
+    }
+
+    #[test]
+    pub fn languages() {
+        assert_eq!(0, MnemonicLanguage::English.code());
+        assert_eq!(9, MnemonicLanguage::Portuguese.code());
+        assert_eq!(Some(MnemonicLanguage::Korean), MnemonicLanguage::from_code(2));
+        assert_eq!(None, MnemonicLanguage::from_code(10));
+    }
+
+    #[test]
+    pub fn err_parse_invalid() {
+        assert_eq!(Err(Error::InvalidPurpose(44)), Bip85Path::from_str("m/44'/2'/0'"));
+        assert_eq!(Err(Error::InvalidStructure), Bip85Path::from_str("m/83696968'/2'/0"));
+        assert_eq!(Err(Error::InvalidStructure), Bip85Path::from_str("m/83696968'/3'/0'"));
+        assert_eq!(Err(Error::InvalidStructure), Bip85Path::from_str("m/83696968'/39'/10'/12'/0'"));
+        assert_eq!(Err(Error::InvalidStructure), Bip85Path::from_str("m/83696968'/39'/0'/13'/0'"));
+        assert_eq!(Err(Error::InvalidLength(4)), Bip85Path::from_str("m/83696968'/2'/0'/0'"));
+        assert_eq!(Err(Error::InvalidLength(3)), Bip85Path::from_str("m/83696968'/128169'/64'"));
+        assert_eq!(Err(Error::InvalidStructure), Bip85Path::from_str("m/83696968'/128169'/65'/0'"));
+    }
+}
+
+#[cfg(all(test, feature = "with-bitcoin"))]
+mod tests_with_bitcoin {
+    use super::*;
+    use crate::CustomHDPath;
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki#test-vectors
+    fn master() -> ExtendedPrivateKey {
+        ExtendedPrivateKey::new(Xpriv::from_str("xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb").unwrap())
+    }
+
+    fn hex(value: &[u8]) -> String {
+        value.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    pub fn entropy_vectors() {
+        let secp = Secp256k1::new();
+        // test cases 1 and 2 use a non-application path, so calculate with a generic path
+        let cases = [
+            ("m/83696968'/0'/0'", "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f00b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7"),
+            ("m/83696968'/0'/1'", "70c6e3e8ebee8dc4c0dbba66076819bb8c09672527c4277ca8729532ad711872218f826919f6b67218adde99018a6df9095ab2b58d803b5b93ec9802085a690e"),
+        ];
+        for (path, expected) in cases.iter() {
+            let key = master().derive(&secp, &CustomHDPath::from_str(path).unwrap()).unwrap();
+            let mut engine = hmac::HmacEngine::<sha512::Hash>::new(b"bip-entropy-from-k");
+            engine.input(&key.expose_secret().private_key.secret_bytes());
+            assert_eq!(*expected, hex(hmac::Hmac::<sha512::Hash>::from_engine(engine).as_byte_array()));
+        }
+    }
+
+    #[test]
+    pub fn hex_vector() {
+        let secp = Secp256k1::new();
+        let hd_path = Bip85Path::new(Application::Hex { num_bytes: 64 }, 0).unwrap();
+        assert_eq!(
+            "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c",
+            hd_path.to_hex(&secp, &master()).unwrap().as_str()
+        );
+    }
+
+    #[test]
+    pub fn wif_vector() {
+        let secp = Secp256k1::new();
+        let hd_path = Bip85Path::new(Application::Wif, 0).unwrap();
+        assert_eq!("Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp", hd_path.to_wif(&secp, &master()).unwrap().as_str());
+    }
+
+    #[test]
+    pub fn xprv_vector() {
+        let secp = Secp256k1::new();
+        let hd_path = Bip85Path::new(Application::Xprv, 0).unwrap();
+        let xprv = hd_path.to_xprv(&secp, &master()).unwrap();
+        assert_eq!(
+            "xprv9s21ZrQH143K2srSbCSg4m4kLvPMzcWydgmKEnMmoZUurYuBuYG46c6P71UGXMzmriLzCCBvKQWBUv3vPB3m1SATMhp3uEjXHJ42jFg7myX",
+            xprv.expose_secret().to_string()
+        );
+    }
+
+    #[test]
+    pub fn password_vector() {
+        let secp = Secp256k1::new();
+        let hd_path = Bip85Path::new(Application::Password { length: 21 }, 0).unwrap();
+        assert_eq!("dKLoepugzdVJvdL56ogNV", hd_path.to_password(&secp, &master()).unwrap().as_str());
+    }
+
+    #[test]
+    pub fn err_other_application() {
+        let secp = Secp256k1::new();
+        let hd_path = Bip85Path::new(Application::Wif, 0).unwrap();
+        assert_eq!(Err(Error::InvalidStructure), hd_path.to_xprv(&secp, &master()));
+        assert!(hd_path.to_hex(&secp, &master()).is_err());
+        assert!(hd_path.to_password(&secp, &master()).is_err());
+    }
+
+    #[test]
+    pub fn encode_base64() {
+        assert_eq!("", base64(b"").as_str());
+        assert_eq!("Zg==", base64(b"f").as_str());
+        assert_eq!("Zm8=", base64(b"fo").as_str());
+        assert_eq!("Zm9v", base64(b"foo").as_str());
+        assert_eq!("Zm9vYmFy", base64(b"foobar").as_str());
+    }
+}
+
+#[cfg(all(test, feature = "bip39"))]
+mod tests_with_bip39 {
+    use super::*;
+
+    fn master() -> ExtendedPrivateKey {
+        ExtendedPrivateKey::new(Xpriv::from_str("xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb").unwrap())
+    }
+
+    #[test]
+    pub fn mnemonic_vectors() {
+        let secp = Secp256k1::new();
+        let cases = [
+            (12, "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"),
+            (18, "near account window bike charge season chef number sketch tomorrow excuse sniff circle vital hockey outdoor supply token"),
+            (24, "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget divorce twin tonight reason outdoor destroy simple truth cigar social volcano"),
+        ];
+        for (words, expected) in cases.iter() {
+            let hd_path = Bip85Path::new(Application::Mnemonic { language: MnemonicLanguage::English, words: *words }, 0).unwrap();
+            assert_eq!(*expected, hd_path.to_mnemonic(&secp, &master()).unwrap().as_str());
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use crate::{AccountHDPath, HDPath, PathValue, Purpose, ShortHDPath, StandardHDPath};
+use std::convert::TryFrom;
+
+/// Type of the script (and so the address) used for keys on a path
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ScriptType {
+    /// Pay to Public Key Hash (BIP-44)
+    P2pkh,
+    /// P2WPKH wrapped into P2SH (BIP-49)
+    P2shP2wpkh,
+    /// Native SegWit Pay to Witness Public Key Hash (BIP-84)
+    P2wpkh,
+    /// Taproot, key-path only (BIP-86)
+    P2tr,
+    /// P2WSH multisig wrapped into P2SH (BIP-48 script type `1'`)
+    P2shP2wsh,
+    /// Native SegWit multisig (BIP-48 script type `2'`)
+    P2wsh,
+}
+
+impl ScriptType {
+
+    /// Script type defined by the purpose, for BIP-44, BIP-49, BIP-84 and BIP-86
+    pub fn from_purpose(purpose: &Purpose) -> Option<ScriptType> {
+        match purpose.as_value().as_number() {
+            44 => Some(ScriptType::P2pkh),
+            49 => Some(ScriptType::P2shP2wpkh),
+            84 => Some(ScriptType::P2wpkh),
+            86 => Some(ScriptType::P2tr),
+            _ => None
+        }
+    }
+
+    /// Script type for the BIP-48 `script_type'` element
+    pub fn from_multisig(value: u32) -> Option<ScriptType> {
+        match value {
+            1 => Some(ScriptType::P2shP2wsh),
+            2 => Some(ScriptType::P2wsh),
+            _ => None
+        }
+    }
+}
+
+/// Kind of an HD Path, with its decoded fields. See [`classify`](fn.classify.html)
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum PathKind {
+    /// BIP-44, BIP-49, BIP-84 or BIP-86 address path, `m/purpose'/coin_type'/account'/change/index`
+    Standard {
+        path: StandardHDPath,
+        script_type: ScriptType,
+    },
+    /// BIP-44, BIP-49, BIP-84 or BIP-86 account path, `m/purpose'/coin_type'/account'`
+    Account {
+        path: AccountHDPath,
+        script_type: ScriptType,
+    },
+    /// BIP-48 multisig address path, `m/48'/coin_type'/account'/script_type'/change/index`
+    Multisig {
+        coin_type: u32,
+        account: u32,
+        script_type: ScriptType,
+        change: u32,
+        index: u32,
+    },
+    /// BIP-48 multisig account path, `m/48'/coin_type'/account'/script_type'`
+    MultisigAccount {
+        coin_type: u32,
+        account: u32,
+        script_type: ScriptType,
+    },
+    /// Ledger Live path for Ethereum, `m/44'/60'/account'/0/0`, where each account has a single address.
+    /// Note that the first account `m/44'/60'/0'/0/0` is classified as `Standard`, because it's the same as for the standard path.
+    LedgerLive {
+        account: u32,
+    },
+    /// Path without the change element, `m/purpose'/coin_type'/account'/index`, used by old Ethereum wallets
+    /// (e.g. legacy Ledger and MyEtherWallet path `m/44'/60'/0'/index`)
+    Short(ShortHDPath),
+    /// Electrum path `m/change/index`, where change is `0` or `1`
+    Electrum {
+        change: u32,
+        index: u32,
+    },
+    /// Any other path
+    Unknown,
+}
+
+impl PathKind {
+
+    /// Coin type of the path, if the path has it
+    pub fn coin_type(&self) -> Option<u32> {
+        match self {
+            PathKind::Standard { path, .. } => Some(path.coin_type()),
+            PathKind::Account { path, .. } => Some(path.coin_type()),
+            PathKind::Multisig { coin_type, .. } => Some(*coin_type),
+            PathKind::MultisigAccount { coin_type, .. } => Some(*coin_type),
+            PathKind::LedgerLive { .. } => Some(60),
+            PathKind::Short(path) => Some(path.coin_type),
+            PathKind::Electrum { .. } | PathKind::Unknown => None,
+        }
+    }
+
+    /// Account number of the path, if the path has it
+    pub fn account(&self) -> Option<u32> {
+        match self {
+            PathKind::Standard { path, .. } => Some(path.account()),
+            PathKind::Account { path, .. } => Some(path.account()),
+            PathKind::Multisig { account, .. } => Some(*account),
+            PathKind::MultisigAccount { account, .. } => Some(*account),
+            PathKind::LedgerLive { account } => Some(*account),
+            PathKind::Short(path) => Some(path.account),
+            PathKind::Electrum { .. } | PathKind::Unknown => None,
+        }
+    }
+
+    /// Script type of the path, if it's defined by the path
+    pub fn script_type(&self) -> Option<ScriptType> {
+        match self {
+            PathKind::Standard { script_type, .. } => Some(*script_type),
+            PathKind::Account { script_type, .. } => Some(*script_type),
+            PathKind::Multisig { script_type, .. } => Some(*script_type),
+            PathKind::MultisigAccount { script_type, .. } => Some(*script_type),
+            PathKind::LedgerLive { .. } | PathKind::Short(_) | PathKind::Electrum { .. } | PathKind::Unknown => None,
+        }
+    }
+}
+
+/// Detect what kind of path it is, i.e. one of standard BIP-44/49/84/86 paths, a BIP-48 multisig, or a path used by
+/// a particular wallet.
+///
+/// # Usage
+/// ```
+/// use hdpath::{classify, CustomHDPath, PathKind, ScriptType};
+/// # use std::str::FromStr;
+///
+/// let hd_path = CustomHDPath::from_str("m/48'/0'/0'/2'/0/1").unwrap();
+/// assert_eq!(
+///     PathKind::Multisig { coin_type: 0, account: 0, script_type: ScriptType::P2wsh, change: 0, index: 1 },
+///     classify(&hd_path)
+/// );
+/// ```
+pub fn classify(hd_path: &dyn HDPath) -> PathKind {
+    let custom = hd_path.as_custom();
+    match custom.0.len() {
+        2 => {
+            if let [PathValue::Normal(change), PathValue::Normal(index)] = custom.0[..] {
+                if change <= 1 {
+                    return PathKind::Electrum { change, index }
+                }
+            }
+            PathKind::Unknown
+        }
+        3 => {
+            if let Ok(path) = AccountHDPath::try_from(custom) {
+                if let Some(script_type) = ScriptType::from_purpose(path.purpose()) {
+                    return PathKind::Account { path, script_type }
+                }
+            }
+            PathKind::Unknown
+        }
+        4 => {
+            if let [PathValue::Hardened(48), PathValue::Hardened(coin_type), PathValue::Hardened(account), PathValue::Hardened(script)] = custom.0[..] {
+                return match ScriptType::from_multisig(script) {
+                    Some(script_type) => PathKind::MultisigAccount { coin_type, account, script_type },
+                    None => PathKind::Unknown
+                }
+            }
+            match ShortHDPath::try_from(custom) {
+                Ok(path) => PathKind::Short(path),
+                Err(_) => PathKind::Unknown
+            }
+        }
+        5 => {
+            match StandardHDPath::try_from(custom) {
+                Ok(path) => {
+                    if *path.purpose() == Purpose::Pubkey && path.coin_type() == 60
+                        && path.account() > 0 && path.change() == 0 && path.index() == 0 {
+                        return PathKind::LedgerLive { account: path.account() }
+                    }
+                    match ScriptType::from_purpose(path.purpose()) {
+                        Some(script_type) => PathKind::Standard { path, script_type },
+                        None => PathKind::Unknown
+                    }
+                }
+                Err(_) => PathKind::Unknown
+            }
+        }
+        6 => {
+            if let [PathValue::Hardened(48), PathValue::Hardened(coin_type), PathValue::Hardened(account), PathValue::Hardened(script), PathValue::Normal(change), PathValue::Normal(index)] = custom.0[..] {
+                if let Some(script_type) = ScriptType::from_multisig(script) {
+                    return PathKind::Multisig { coin_type, account, script_type, change, index }
+                }
+            }
+            PathKind::Unknown
+        }
+        _ => PathKind::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CustomHDPath;
+    use std::str::FromStr;
+
+    fn classify_str(value: &str) -> PathKind {
+        classify(&CustomHDPath::from_str(value).unwrap())
+    }
+
+    #[test]
+    pub fn standard() {
+        assert_eq!(
+            PathKind::Standard { path: StandardHDPath::from_str("m/44'/0'/0'/0/0").unwrap(), script_type: ScriptType::P2pkh },
+            classify_str("m/44'/0'/0'/0/0")
+        );
+        assert_eq!(
+            PathKind::Standard { path: StandardHDPath::from_str("m/49'/1'/2'/1/3").unwrap(), script_type: ScriptType::P2shP2wpkh },
+            classify_str("m/49'/1'/2'/1/3")
+        );
+        assert_eq!(
+            PathKind::Standard { path: StandardHDPath::from_str("m/84'/0'/0'/1/15").unwrap(), script_type: ScriptType::P2wpkh },
+            classify_str("m/84'/0'/0'/1/15")
+        );
+        assert_eq!(
+            PathKind::Standard { path: StandardHDPath::from_str("m/86'/0'/5'/0/0").unwrap(), script_type: ScriptType::P2tr },
+            classify_str("m/86'/0'/5'/0/0")
+        );
+        assert_eq!(
+            PathKind::Standard { path: StandardHDPath::from_str("m/44'/60'/0'/0/0").unwrap(), script_type: ScriptType::P2pkh },
+            classify_str("m/44'/60'/0'/0/0")
+        );
+        assert_eq!(
+            PathKind::Standard { path: StandardHDPath::from_str("m/44'/60'/0'/0/5").unwrap(), script_type: ScriptType::P2pkh },
+            classify_str("m/44'/60'/0'/0/5")
+        );
+    }
+
+    #[test]
+    pub fn standard_from_typed() {
+        let hd_path = StandardHDPath::from_str("m/84'/0'/0'/0/0").unwrap();
+        assert_eq!(
+            PathKind::Standard { path: hd_path.clone(), script_type: ScriptType::P2wpkh },
+            classify(&hd_path)
+        );
+        let hd_path = AccountHDPath::from_str("m/84'/0'/3'").unwrap();
+        assert_eq!(
+            PathKind::Account { path: hd_path.clone(), script_type: ScriptType::P2wpkh },
+            classify(&hd_path)
+        );
+    }
+
+    #[test]
+    pub fn account() {
+        assert_eq!(
+            PathKind::Account { path: AccountHDPath::from_str("m/84'/0'/3'").unwrap(), script_type: ScriptType::P2wpkh },
+            classify_str("m/84'/0'/3'")
+        );
+        assert_eq!(PathKind::Unknown, classify_str("m/101'/0'/3'"));
+    }
+
+    #[test]
+    pub fn multisig() {
+        assert_eq!(
+            PathKind::Multisig { coin_type: 0, account: 0, script_type: ScriptType::P2wsh, change: 0, index: 1 },
+            classify_str("m/48'/0'/0'/2'/0/1")
+        );
+        assert_eq!(
+            PathKind::Multisig { coin_type: 1, account: 3, script_type: ScriptType::P2shP2wsh, change: 1, index: 0 },
+            classify_str("m/48'/1'/3'/1'/1/0")
+        );
+        assert_eq!(
+            PathKind::MultisigAccount { coin_type: 0, account: 0, script_type: ScriptType::P2wsh },
+            classify_str("m/48'/0'/0'/2'")
+        );
+        assert_eq!(PathKind::Unknown, classify_str("m/48'/0'/0'/5'/0/1"));
+        assert_eq!(PathKind::Unknown, classify_str("m/48'/0'/0'/2/0/1"));
+        assert_eq!(PathKind::Unknown, classify_str("m/48'/0'/0'/5'"));
+    }
+
+    #[test]
+    pub fn ledger_live() {
+        assert_eq!(PathKind::LedgerLive { account: 1 }, classify_str("m/44'/60'/1'/0/0"));
+        assert_eq!(PathKind::LedgerLive { account: 4 }, classify_str("m/44'/60'/4'/0/0"));
+        assert_eq!(Some(4), classify_str("m/44'/60'/4'/0/0").account());
+    }
+
+    #[test]
+    pub fn short() {
+        assert_eq!(
+            PathKind::Short(ShortHDPath::from_str("m/44'/60'/0'/3").unwrap()),
+            classify_str("m/44'/60'/0'/3")
+        );
+    }
+
+    #[test]
+    pub fn electrum() {
+        assert_eq!(PathKind::Electrum { change: 0, index: 5 }, classify_str("m/0/5"));
+        assert_eq!(PathKind::Electrum { change: 1, index: 0 }, classify_str("m/1/0"));
+        assert_eq!(PathKind::Unknown, classify_str("m/2/0"));
+        assert_eq!(PathKind::Unknown, classify_str("m/0'/0"));
+    }
+
+    #[test]
+    pub fn unknown() {
+        let paths = [
+            "m/0", "m/44'/0'/0'/0'/0", "m/44'/0'/0'/0/0/0", "m/101'/0'/0'/0/0",
+            "m/44/0'/0'/0/0", "m/1/2/3/4/5/6/7",
+        ];
+        for p in paths {
+            assert_eq!(PathKind::Unknown, classify_str(p), "path: {}", p);
+        }
+    }
+
+    #[test]
+    pub fn decoded_fields() {
+        let kind = classify_str("m/84'/2'/7'/1/3");
+        assert_eq!(Some(2), kind.coin_type());
+        assert_eq!(Some(7), kind.account());
+        assert_eq!(Some(ScriptType::P2wpkh), kind.script_type());
+        let kind = classify_str("m/0/3");
+        assert_eq!(None, kind.coin_type());
+        assert_eq!(None, kind.account());
+        assert_eq!(None, kind.script_type());
+    }
+}
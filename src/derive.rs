@@ -0,0 +1,170 @@
+use crate::{AccountHDPath, Error, ExtendedPrivateKey, PathValue, Seed, StandardHDPath};
+use crate::path_value::FIRST_BIT;
+use bitcoin::NetworkKind;
+use bitcoin::bip32::{ChildNumber, Xpub};
+use bitcoin::secp256k1::{PublicKey, Secp256k1, Signing, Verification};
+use std::ops::Range;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Extended Public Key of an account, i.e. a key at `m/purpose'/coin_type'/account'`, which is used
+/// to derive public keys of the addresses on that account.
+///
+/// Since all the elements after account are _not hardened_ it can derive all the addresses without
+/// access to a private key. The account and change level nodes are derived only once, so it's the
+/// preferred way to derive a large number of addresses.
+///
+/// With the `parallel` feature enabled the addresses are derived on multiple threads (using `rayon`).
+///
+/// # Usage
+/// ```
+/// use hdpath::{AccountHDPath, AccountKeys, Purpose, Seed};
+/// use bitcoin::secp256k1::Secp256k1;
+/// use bitcoin::NetworkKind;
+///
+/// let secp = Secp256k1::new();
+/// let master = Seed::from_bytes(&[0u8; 32]).unwrap().to_master(NetworkKind::Main).unwrap();
+/// let account = AccountKeys::from_master(&secp, &master, &AccountHDPath::new(Purpose::Witness, 0, 0)).unwrap();
+///
+/// // public keys for m/84'/0'/0'/0/0 .. m/84'/0'/0'/0/99
+/// let keys = account.derive_range(&secp, 0, 0..100).unwrap();
+/// assert_eq!(100, keys.len());
+/// assert_eq!("m/84'/0'/0'/0/99", keys[99].0.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountKeys {
+    path: AccountHDPath,
+    xpub: Xpub,
+}
+
+impl AccountKeys {
+
+    /// Use already known Extended Public Key of the account. The caller is responsible to provide
+    /// a key that actually corresponds to the `path`.
+    pub fn new(path: AccountHDPath, xpub: Xpub) -> AccountKeys {
+        AccountKeys {
+            path,
+            xpub,
+        }
+    }
+
+    /// Derive the account key from the master (root) private key
+    pub fn from_master<C: Signing>(secp: &Secp256k1<C>, master: &ExtendedPrivateKey, path: &AccountHDPath) -> Result<AccountKeys, Error> {
+        let account = master.derive(secp, path)?;
+        Ok(AccountKeys {
+            path: path.clone(),
+            xpub: account.to_xpub(secp),
+        })
+    }
+
+    /// Derive the account key from the seed, for the network
+    pub fn from_seed<C: Signing>(secp: &Secp256k1<C>, seed: &Seed, network: NetworkKind, path: &AccountHDPath) -> Result<AccountKeys, Error> {
+        let account = seed.derive_priv(secp, network, path)?;
+        Ok(AccountKeys {
+            path: path.clone(),
+            xpub: account.to_xpub(secp),
+        })
+    }
+
+    pub fn path(&self) -> &AccountHDPath {
+        &self.path
+    }
+
+    pub fn xpub(&self) -> &Xpub {
+        &self.xpub
+    }
+
+    /// Derive public keys for all addresses on the `change` chain with indexes in the `range`.
+    /// Returns pairs of the address HD Path and its public key, in the same order as the range.
+    ///
+    /// Returns error if the change or the range goes into the _hardened_ space.
+    pub fn derive_range<C: Verification>(&self, secp: &Secp256k1<C>, change: u32, range: Range<u32>) -> Result<Vec<(StandardHDPath, PublicKey)>, Error> {
+        if !PathValue::is_ok(change) || range.end > FIRST_BIT {
+            return Err(Error::HighBitIsSet)
+        }
+        let chain = self.xpub.ckd_pub(secp, ChildNumber::Normal { index: change })?;
+        let derive_one = |index: u32| -> Result<(StandardHDPath, PublicKey), Error> {
+            let (tweak, _) = chain.ckd_pub_tweak(ChildNumber::Normal { index })?;
+            let key = chain.public_key.add_exp_tweak(secp, &tweak.into())
+                .map_err(|_| Error::KeyDerivation)?;
+            let path = StandardHDPath::new(
+                self.path.purpose().clone(), self.path.coin_type(), self.path.account(), change, index
+            );
+            Ok((path, key))
+        };
+        #[cfg(feature = "parallel")]
+        let result = range.into_par_iter().map(derive_one).collect();
+        #[cfg(not(feature = "parallel"))]
+        let result = range.map(derive_one).collect();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Purpose;
+    use bitcoin::NetworkKind;
+    use std::str::FromStr;
+
+    fn seed() -> Seed {
+        // seed from BIP-32 Test Vector 1
+        Seed::from_bytes(&[0x00u8, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f]).unwrap()
+    }
+
+    fn master() -> ExtendedPrivateKey {
+        seed().to_master(NetworkKind::Main).unwrap()
+    }
+
+    #[test]
+    pub fn same_as_single_derivation() {
+        let secp = Secp256k1::new();
+        let master = master();
+        let account = AccountKeys::from_master(&secp, &master, &AccountHDPath::new(Purpose::Witness, 0, 3)).unwrap();
+        for change in 0..2 {
+            let act = account.derive_range(&secp, change, 10..40).unwrap();
+            assert_eq!(30, act.len());
+            for (i, (path, key)) in act.iter().enumerate() {
+                assert_eq!(StandardHDPath::new(Purpose::Witness, 0, 3, change, 10 + i as u32), *path);
+                let exp = master.derive(&secp, path).unwrap();
+                assert_eq!(exp.public_key(&secp), *key, "path: {}", path);
+            }
+        }
+    }
+
+    #[test]
+    pub fn use_provided_xpub() {
+        let secp = Secp256k1::new();
+        let path = AccountHDPath::from_str("m/44'/0'/0'").unwrap();
+        let xpub = master().derive(&secp, &path).unwrap().to_xpub(&secp);
+        let account = AccountKeys::new(path, xpub);
+        let act = account.derive_range(&secp, 1, 0..1).unwrap();
+        let exp = master().derive(&secp, &StandardHDPath::from_str("m/44'/0'/0'/1/0").unwrap()).unwrap();
+        assert_eq!(exp.public_key(&secp), act[0].1);
+    }
+
+    #[test]
+    pub fn from_seed() {
+        let secp = Secp256k1::new();
+        let path = AccountHDPath::from_str("m/84'/0'/1'").unwrap();
+        assert_eq!(
+            AccountKeys::from_master(&secp, &master(), &path).unwrap(),
+            AccountKeys::from_seed(&secp, &seed(), NetworkKind::Main, &path).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn empty_range() {
+        let secp = Secp256k1::new();
+        let account = AccountKeys::from_master(&secp, &master(), &AccountHDPath::new(Purpose::Pubkey, 0, 0)).unwrap();
+        assert!(account.derive_range(&secp, 0, 5..5).unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn err_on_hardened_range() {
+        let secp = Secp256k1::new();
+        let account = AccountKeys::from_master(&secp, &master(), &AccountHDPath::new(Purpose::Pubkey, 0, 0)).unwrap();
+        assert_eq!(Err(Error::HighBitIsSet), account.derive_range(&secp, 0, 0x7ffffff0..0x80000001));
+        assert_eq!(Err(Error::HighBitIsSet), account.derive_range(&secp, 0x80000000, 0..10));
+    }
+}
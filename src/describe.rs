@@ -0,0 +1,218 @@
+use crate::{classify, HDPath, PathKind, ScriptType};
+
+/// Format of the path description, see [`describe`](fn.describe.html)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DescriptionFormat {
+    /// Compact form, e.g. `Bitcoin Native SegWit #2, change #7`
+    Short,
+    /// Complete form, e.g. `Bitcoin Native SegWit (BIP-84), account #2, change address #7`
+    Long,
+}
+
+/// Known coin types, as `(coin_type, name, is_utxo)`.
+/// See [SLIP-44](https://github.com/satoshilabs/slips/blob/master/slip-0044.md)
+const COINS: &[(u32, &str, bool)] = &[
+    (0, "Bitcoin", true),
+    (1, "Bitcoin Testnet", true),
+    (2, "Litecoin", true),
+    (3, "Dogecoin", true),
+    (5, "Dash", true),
+    (60, "Ethereum", false),
+    (61, "Ethereum Classic", false),
+    (118, "Cosmos", false),
+    (133, "Zcash", true),
+    (144, "XRP", false),
+    (145, "Bitcoin Cash", true),
+    (195, "Tron", false),
+    (354, "Polkadot", false),
+    (501, "Solana", false),
+    (1815, "Cardano", false),
+];
+
+/// Name of the coin for a [SLIP-44](https://github.com/satoshilabs/slips/blob/master/slip-0044.md) coin type,
+/// if it's one of the commonly used coins.
+pub fn coin_name(coin_type: u32) -> Option<&'static str> {
+    COINS.iter().find(|c| c.0 == coin_type).map(|c| c.1)
+}
+
+fn coin(coin_type: u32) -> String {
+    match coin_name(coin_type) {
+        Some(name) => name.to_string(),
+        None => format!("Coin #{}", coin_type)
+    }
+}
+
+fn is_utxo(coin_type: u32) -> bool {
+    COINS.iter().any(|c| c.0 == coin_type && c.2)
+}
+
+fn script_name(script_type: ScriptType) -> &'static str {
+    match script_type {
+        ScriptType::P2pkh => "Legacy",
+        ScriptType::P2shP2wpkh => "SegWit",
+        ScriptType::P2wpkh => "Native SegWit",
+        ScriptType::P2tr => "Taproot",
+        ScriptType::P2shP2wsh => "Multisig SegWit",
+        ScriptType::P2wsh => "Multisig Native SegWit",
+    }
+}
+
+fn address(change: u32, index: u32, format: DescriptionFormat, utxo: bool) -> String {
+    match (change, format) {
+        (0, DescriptionFormat::Long) if utxo => format!("receive address #{}", index),
+        (1, DescriptionFormat::Long) if utxo => format!("change address #{}", index),
+        (1, DescriptionFormat::Short) if utxo => format!("change #{}", index),
+        (0, _) => format!("address #{}", index),
+        (_, _) => format!("chain #{}, address #{}", change, index),
+    }
+}
+
+/// Human-readable description of an HD Path, based on its kind (see [`classify`](fn.classify.html)),
+/// the purpose and the coin type. Account and address numbers are the actual values in the path, i.e. start with `#0`.
+///
+/// The text is stable, i.e. the same path gives the same description in all patch versions of the crate.
+///
+/// # Usage
+/// ```
+/// use hdpath::{describe, DescriptionFormat, StandardHDPath};
+/// # use std::str::FromStr;
+///
+/// let hd_path = StandardHDPath::from_str("m/84'/0'/2'/1/7").unwrap();
+/// assert_eq!(
+///     "Bitcoin Native SegWit (BIP-84), account #2, change address #7",
+///     describe(&hd_path, DescriptionFormat::Long)
+/// );
+/// assert_eq!(
+///     "Bitcoin Native SegWit #2, change #7",
+///     describe(&hd_path, DescriptionFormat::Short)
+/// );
+/// ```
+pub fn describe(hd_path: &dyn HDPath, format: DescriptionFormat) -> String {
+    let long = format == DescriptionFormat::Long;
+    match classify(hd_path) {
+        PathKind::Standard { path, script_type } => {
+            let utxo = is_utxo(path.coin_type());
+            let name = if utxo {
+                format!("{} {}", coin(path.coin_type()), script_name(script_type))
+            } else {
+                coin(path.coin_type())
+            };
+            let address = address(path.change(), path.index(), format, utxo);
+            if long {
+                format!("{} (BIP-{}), account #{}, {}", name, path.purpose().as_value().as_number(), path.account(), address)
+            } else {
+                format!("{} #{}, {}", name, path.account(), address)
+            }
+        }
+        PathKind::Account { path, script_type } => {
+            let name = if is_utxo(path.coin_type()) {
+                format!("{} {}", coin(path.coin_type()), script_name(script_type))
+            } else {
+                coin(path.coin_type())
+            };
+            if long {
+                format!("{} (BIP-{}), account #{}", name, path.purpose().as_value().as_number(), path.account())
+            } else {
+                format!("{} #{}", name, path.account())
+            }
+        }
+        PathKind::Multisig { coin_type, account, script_type, change, index } => {
+            let address = address(change, index, format, true);
+            if long {
+                format!("{} {} (BIP-48), account #{}, {}", coin(coin_type), script_name(script_type), account, address)
+            } else {
+                format!("{} {} #{}, {}", coin(coin_type), script_name(script_type), account, address)
+            }
+        }
+        PathKind::MultisigAccount { coin_type, account, script_type } => {
+            if long {
+                format!("{} {} (BIP-48), account #{}", coin(coin_type), script_name(script_type), account)
+            } else {
+                format!("{} {} #{}", coin(coin_type), script_name(script_type), account)
+            }
+        }
+        PathKind::LedgerLive { account } => {
+            if long {
+                format!("Ethereum (Ledger Live), account #{}", account)
+            } else {
+                format!("Ethereum #{}", account)
+            }
+        }
+        PathKind::Short(path) => {
+            if long {
+                format!("{} (legacy), account #{}, address #{}", coin(path.coin_type), path.account, path.index)
+            } else {
+                format!("{} #{}, address #{}", coin(path.coin_type), path.account, path.index)
+            }
+        }
+        PathKind::Electrum { change, index } => {
+            match (change, long) {
+                (0, true) => format!("Electrum, receive address #{}", index),
+                (_, true) => format!("Electrum, change address #{}", index),
+                (0, false) => format!("Electrum address #{}", index),
+                (_, false) => format!("Electrum change #{}", index),
+            }
+        }
+        PathKind::Unknown => {
+            let path = hd_path.as_custom().to_string();
+            if long {
+                format!("Custom path {}", path)
+            } else {
+                path
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountHDPath, CustomHDPath, StandardHDPath};
+    use std::str::FromStr;
+
+    fn both(value: &str) -> (String, String) {
+        let hd_path = CustomHDPath::from_str(value).unwrap();
+        (describe(&hd_path, DescriptionFormat::Long), describe(&hd_path, DescriptionFormat::Short))
+    }
+
+    #[test]
+    pub fn snapshot() {
+        let expected = [
+            ("m/44'/0'/0'/0/0", "Bitcoin Legacy (BIP-44), account #0, receive address #0", "Bitcoin Legacy #0, address #0"),
+            ("m/49'/0'/1'/0/3", "Bitcoin SegWit (BIP-49), account #1, receive address #3", "Bitcoin SegWit #1, address #3"),
+            ("m/84'/0'/2'/1/7", "Bitcoin Native SegWit (BIP-84), account #2, change address #7", "Bitcoin Native SegWit #2, change #7"),
+            ("m/86'/1'/0'/0/1", "Bitcoin Testnet Taproot (BIP-86), account #0, receive address #1", "Bitcoin Testnet Taproot #0, address #1"),
+            ("m/84'/2'/0'/5/1", "Litecoin Native SegWit (BIP-84), account #0, chain #5, address #1", "Litecoin Native SegWit #0, chain #5, address #1"),
+            ("m/44'/60'/0'/0/3", "Ethereum (BIP-44), account #0, address #3", "Ethereum #0, address #3"),
+            ("m/44'/60'/0'/1/3", "Ethereum (BIP-44), account #0, chain #1, address #3", "Ethereum #0, chain #1, address #3"),
+            ("m/44'/60'/4'/0/0", "Ethereum (Ledger Live), account #4", "Ethereum #4"),
+            ("m/44'/60'/0'/3", "Ethereum (legacy), account #0, address #3", "Ethereum #0, address #3"),
+            ("m/44'/12345'/0'/0/0", "Coin #12345 (BIP-44), account #0, address #0", "Coin #12345 #0, address #0"),
+            ("m/84'/0'/3'", "Bitcoin Native SegWit (BIP-84), account #3", "Bitcoin Native SegWit #3"),
+            ("m/44'/60'/3'", "Ethereum (BIP-44), account #3", "Ethereum #3"),
+            ("m/48'/0'/0'/2'/0/1", "Bitcoin Multisig Native SegWit (BIP-48), account #0, receive address #1", "Bitcoin Multisig Native SegWit #0, address #1"),
+            ("m/48'/1'/1'/1'", "Bitcoin Testnet Multisig SegWit (BIP-48), account #1", "Bitcoin Testnet Multisig SegWit #1"),
+            ("m/0/5", "Electrum, receive address #5", "Electrum address #5"),
+            ("m/1/2", "Electrum, change address #2", "Electrum change #2"),
+            ("m/1/2/3", "Custom path m/1/2/3", "m/1/2/3"),
+        ];
+        for (path, long, short) in expected {
+            assert_eq!((long.to_string(), short.to_string()), both(path), "path: {}", path);
+        }
+    }
+
+    #[test]
+    pub fn describe_typed() {
+        let hd_path = StandardHDPath::from_str("m/84'/0'/2'/1/7").unwrap();
+        assert_eq!("Bitcoin Native SegWit (BIP-84), account #2, change address #7", describe(&hd_path, DescriptionFormat::Long));
+        let hd_path = AccountHDPath::from_str("m/84'/0'/2'").unwrap();
+        assert_eq!("Bitcoin Native SegWit #2", describe(&hd_path, DescriptionFormat::Short));
+    }
+
+    #[test]
+    pub fn known_coins() {
+        assert_eq!(Some("Bitcoin"), coin_name(0));
+        assert_eq!(Some("Ethereum"), coin_name(60));
+        assert_eq!(None, coin_name(12345));
+    }
+}
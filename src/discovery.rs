@@ -0,0 +1,463 @@
+use crate::{AccountHDPath, Error, Purpose, StandardHDPath};
+use std::fmt;
+use std::future::Future;
+
+/// Source of the transaction history for addresses, used by [`AccountDiscovery`](struct.AccountDiscovery.html)
+///
+/// It's implemented for closures `Fn(&StandardHDPath) -> Result<bool, E>`
+pub trait HistoryOracle {
+    type Error;
+
+    /// Check if the address at the path has any transactions
+    fn has_history(&self, hd_path: &StandardHDPath) -> Result<bool, Self::Error>;
+}
+
+impl<F, E> HistoryOracle for F where F: Fn(&StandardHDPath) -> Result<bool, E> {
+    type Error = E;
+
+    fn has_history(&self, hd_path: &StandardHDPath) -> Result<bool, Self::Error> {
+        self(hd_path)
+    }
+}
+
+/// Asynchronous version of [`HistoryOracle`](trait.HistoryOracle.html), e.g. for a network backed history.
+///
+/// The returned future must be `Send`, so the discovery can run on a multi-threaded executor (e.g. with `tokio::spawn`)
+/// when the oracle is `Sync`.
+pub trait AsyncHistoryOracle {
+    type Error;
+
+    /// Check if the address at the path has any transactions
+    fn has_history(&self, hd_path: &StandardHDPath) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+}
+
+/// Error of a gap limit scan over an external source, such as [`AccountDiscovery`](struct.AccountDiscovery.html)
+/// or [`PathIndex`](struct.PathIndex.html)
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ScanError<E> {
+    /// Gap limit is `0`, so no address would be scanned
+    ZeroGapLimit,
+    /// Invalid path or path element
+    InvalidPath(Error),
+    /// Error returned by the source, i.e. the history oracle or the path deriver
+    Source(E),
+}
+
+impl<E> From<Error> for ScanError<E> {
+    fn from(value: Error) -> Self {
+        ScanError::InvalidPath(value)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ScanError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::ZeroGapLimit => write!(f, "Gap limit must be greater than 0"),
+            ScanError::InvalidPath(e) => write!(f, "{}", e),
+            ScanError::Source(e) => write!(f, "Source error: {}", e),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for ScanError<E> {}
+
+/// Account found by [`AccountDiscovery`](struct.AccountDiscovery.html)
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DiscoveredAccount {
+    pub account: AccountHDPath,
+    /// Last used index on the external (receive) chain. An account without history on the external chain is not discovered,
+    /// so it always exists.
+    pub last_external: u32,
+    /// Last used index on the internal (change) chain
+    pub last_internal: Option<u32>,
+}
+
+impl DiscoveredAccount {
+
+    /// Path for the first unused address on the chain (`0` for external, `1` for internal),
+    /// or `None` if the chain is exhausted or the change is invalid
+    pub fn next_unused(&self, change: u32) -> Option<StandardHDPath> {
+        let last = match change {
+            0 => Some(self.last_external),
+            1 => self.last_internal,
+            _ => return None
+        };
+        let index = match last {
+            Some(i) => i.checked_add(1)?,
+            None => 0
+        };
+        self.account.address_at(change, index).ok()
+    }
+}
+
+/// BIP-44 Account Discovery. Scans accounts `0, 1, ...` for each of the purposes, and stops at the first account
+/// without history on its external chain. A chain is scanned until there are _gap limit_ consecutive unused addresses.
+///
+/// See [BIP-44 Account Discovery](https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki#account-discovery)
+///
+/// # Usage
+/// ```
+/// use hdpath::{AccountDiscovery, Purpose, StandardHDPath};
+/// use std::convert::Infallible;
+///
+/// let discovery = AccountDiscovery::new(0, &[Purpose::Witness, Purpose::Taproot]);
+/// // in a real app it would query a blockchain index for the address at the path
+/// let used = |hd_path: &StandardHDPath| -> Result<bool, Infallible> {
+///     Ok(*hd_path.purpose() == Purpose::Witness && hd_path.account() == 0 && hd_path.index() < 5)
+/// };
+/// let accounts = discovery.discover(&used).unwrap();
+/// assert_eq!(1, accounts.len());
+/// assert_eq!("m/84'/0'/0'/0/5", accounts[0].next_unused(0).unwrap().to_string());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AccountDiscovery {
+    pub coin_type: u32,
+    /// Purposes to scan, each with its own accounts
+    pub purposes: Vec<Purpose>,
+    /// Gap limit for the external (receive) chain, default is `20`. Must be greater than `0`
+    pub external_gap_limit: u32,
+    /// Gap limit for the internal (change) chain, default is `20`. Must be greater than `0`
+    pub internal_gap_limit: u32,
+}
+
+pub(crate) const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// State of scanning a single chain of an account
+struct ChainScan {
+    account: AccountHDPath,
+    change: u32,
+    gap_limit: u32,
+    index: u32,
+    last_used: Option<u32>,
+}
+
+impl ChainScan {
+    fn new(account: &AccountHDPath, change: u32, gap_limit: u32) -> ChainScan {
+        ChainScan {
+            account: account.clone(),
+            change,
+            gap_limit,
+            index: 0,
+            last_used: None,
+        }
+    }
+
+    /// Next path to check, or `None` when the gap limit is reached
+    fn next(&self) -> Option<StandardHDPath> {
+        let first_unused = self.last_used.map_or(0, |i| i + 1);
+        if self.index - first_unused >= self.gap_limit {
+            return None
+        }
+        self.account.address_at(self.change, self.index).ok()
+    }
+
+    fn update(&mut self, used: bool) {
+        if used {
+            self.last_used = Some(self.index);
+        }
+        self.index += 1;
+    }
+}
+
+/// State of the whole discovery, shared by the sync and async versions. It goes through the purposes, their accounts
+/// and the chains of each account, giving the next path to check with `next()` and taking the result with `update()`.
+struct DiscoveryScan<'a> {
+    discovery: &'a AccountDiscovery,
+    purpose: usize,
+    account: u32,
+    chain: Option<ChainScan>,
+    last_external: u32,
+    result: Vec<DiscoveredAccount>,
+}
+
+impl<'a> DiscoveryScan<'a> {
+    fn new(discovery: &'a AccountDiscovery) -> DiscoveryScan<'a> {
+        let mut scan = DiscoveryScan {
+            discovery,
+            purpose: 0,
+            account: 0,
+            chain: None,
+            last_external: 0,
+            result: vec![],
+        };
+        scan.chain = scan.start_account();
+        scan
+    }
+
+    /// External chain scan of the current account, or of the first account of the next purpose if the current
+    /// purpose has no more accounts. `None` when all purposes are done.
+    fn start_account(&mut self) -> Option<ChainScan> {
+        while let Some(purpose) = self.discovery.purposes.get(self.purpose) {
+            match self.discovery.account(purpose, self.account) {
+                Some(account) => return Some(ChainScan::new(&account, 0, self.discovery.external_gap_limit)),
+                None => {
+                    self.purpose += 1;
+                    self.account = 0;
+                }
+            }
+        }
+        None
+    }
+
+    /// Next path to check, or `None` when the discovery is finished
+    fn next(&mut self) -> Option<StandardHDPath> {
+        loop {
+            let chain = self.chain.as_ref()?;
+            if let Some(hd_path) = chain.next() {
+                return Some(hd_path)
+            }
+            let account = chain.account.clone();
+            match (chain.change, chain.last_used) {
+                // an account without history stops the scan of the purpose
+                (0, None) => {
+                    self.purpose += 1;
+                    self.account = 0;
+                    self.chain = self.start_account();
+                }
+                (0, Some(last)) => {
+                    self.last_external = last;
+                    self.chain = Some(ChainScan::new(&account, 1, self.discovery.internal_gap_limit));
+                }
+                (_, last_internal) => {
+                    self.result.push(DiscoveredAccount {
+                        account,
+                        last_external: self.last_external,
+                        last_internal,
+                    });
+                    self.account += 1;
+                    self.chain = self.start_account();
+                }
+            }
+        }
+    }
+
+    /// Result of checking the path returned by `next()`
+    fn update(&mut self, used: bool) {
+        if let Some(chain) = self.chain.as_mut() {
+            chain.update(used);
+        }
+    }
+}
+
+impl AccountDiscovery {
+
+    /// Discovery for the coin and purposes with the default gap limits
+    pub fn new(coin_type: u32, purposes: &[Purpose]) -> AccountDiscovery {
+        AccountDiscovery {
+            coin_type,
+            purposes: purposes.to_vec(),
+            external_gap_limit: DEFAULT_GAP_LIMIT,
+            internal_gap_limit: DEFAULT_GAP_LIMIT,
+        }
+    }
+
+    fn account(&self, purpose: &Purpose, account: u32) -> Option<AccountHDPath> {
+        AccountHDPath::try_new(purpose.clone(), self.coin_type, account).ok()
+    }
+
+    fn scan<E>(&self) -> Result<DiscoveryScan<'_>, ScanError<E>> {
+        if self.external_gap_limit == 0 || self.internal_gap_limit == 0 {
+            return Err(ScanError::ZeroGapLimit)
+        }
+        Ok(DiscoveryScan::new(self))
+    }
+
+    /// Find all used accounts, ordered by purpose (in the order of `purposes`) and account number.
+    /// Fails with `ScanError::ZeroGapLimit` if any of the gap limits is `0`
+    pub fn discover<O: HistoryOracle>(&self, oracle: &O) -> Result<Vec<DiscoveredAccount>, ScanError<O::Error>> {
+        let mut scan = self.scan()?;
+        while let Some(hd_path) = scan.next() {
+            scan.update(oracle.has_history(&hd_path).map_err(ScanError::Source)?);
+        }
+        Ok(scan.result)
+    }
+
+    /// Same as [`discover`](#method.discover), but with an asynchronous oracle
+    pub async fn discover_async<O: AsyncHistoryOracle>(&self, oracle: &O) -> Result<Vec<DiscoveredAccount>, ScanError<O::Error>> {
+        let mut scan = self.scan()?;
+        while let Some(hd_path) = scan.next() {
+            scan.update(oracle.has_history(&hd_path).await.map_err(ScanError::Source)?);
+        }
+        Ok(scan.result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::convert::Infallible;
+    use std::pin::pin;
+    use std::str::FromStr;
+    use std::sync::Mutex;
+    use std::task::{Context, Poll, Waker};
+
+    struct History {
+        used: HashSet<StandardHDPath>,
+        checked: Mutex<Vec<StandardHDPath>>,
+    }
+
+    impl History {
+        fn new(used: &[&str]) -> History {
+            History {
+                used: used.iter().map(|p| StandardHDPath::from_str(p).unwrap()).collect(),
+                checked: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    impl HistoryOracle for History {
+        type Error = Infallible;
+
+        fn has_history(&self, hd_path: &StandardHDPath) -> Result<bool, Self::Error> {
+            self.checked.lock().unwrap().push(hd_path.clone());
+            Ok(self.used.contains(hd_path))
+        }
+    }
+
+    impl AsyncHistoryOracle for History {
+        type Error = Infallible;
+
+        async fn has_history(&self, hd_path: &StandardHDPath) -> Result<bool, Self::Error> {
+            HistoryOracle::has_history(self, hd_path)
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(result) = future.as_mut().poll(&mut context) {
+                return result
+            }
+        }
+    }
+
+    #[test]
+    pub fn nothing_used() {
+        let history = History::new(&[]);
+        let discovery = AccountDiscovery::new(0, &[Purpose::Witness]);
+        assert!(discovery.discover(&history).unwrap().is_empty());
+        // only the external chain of the first account
+        assert_eq!(20, history.checked.lock().unwrap().len());
+        assert_eq!("m/84'/0'/0'/0/19", history.checked.lock().unwrap()[19].to_string());
+    }
+
+    #[test]
+    pub fn discover_accounts() {
+        let history = History::new(&[
+            "m/84'/0'/0'/0/0", "m/84'/0'/0'/0/1", "m/84'/0'/0'/1/0",
+            "m/84'/0'/1'/0/19",
+            // after an unused account
+            "m/84'/0'/3'/0/0",
+        ]);
+        let discovery = AccountDiscovery::new(0, &[Purpose::Witness]);
+        let accounts = discovery.discover(&history).unwrap();
+        assert_eq!(vec![
+            DiscoveredAccount {
+                account: AccountHDPath::from_str("m/84'/0'/0'").unwrap(),
+                last_external: 1,
+                last_internal: Some(0),
+            },
+            DiscoveredAccount {
+                account: AccountHDPath::from_str("m/84'/0'/1'").unwrap(),
+                last_external: 19,
+                last_internal: None,
+            },
+        ], accounts);
+        // 22 + 21 for account 0, 40 + 20 for account 1, 20 for account 2
+        assert_eq!(22 + 21 + 40 + 20 + 20, history.checked.lock().unwrap().len());
+    }
+
+    #[test]
+    pub fn gap_limit() {
+        let history = History::new(&["m/44'/0'/0'/0/0", "m/44'/0'/0'/0/5", "m/44'/0'/0'/1/3"]);
+        let mut discovery = AccountDiscovery::new(0, &[Purpose::Pubkey]);
+        discovery.external_gap_limit = 4;
+        discovery.internal_gap_limit = 3;
+        let accounts = discovery.discover(&history).unwrap();
+        assert_eq!(1, accounts.len());
+        assert_eq!(0, accounts[0].last_external);
+        assert_eq!(None, accounts[0].last_internal);
+
+        discovery.external_gap_limit = 5;
+        discovery.internal_gap_limit = 4;
+        let accounts = discovery.discover(&history).unwrap();
+        assert_eq!(5, accounts[0].last_external);
+        assert_eq!(Some(3), accounts[0].last_internal);
+    }
+
+    #[test]
+    pub fn multiple_purposes() {
+        let history = History::new(&["m/44'/0'/0'/0/0", "m/86'/0'/0'/0/3", "m/86'/0'/1'/0/0"]);
+        let discovery = AccountDiscovery::new(0, &[Purpose::Pubkey, Purpose::ScriptHash, Purpose::Witness, Purpose::Taproot]);
+        let accounts: Vec<String> = discovery.discover(&history).unwrap().iter()
+            .map(|a| a.account.to_string())
+            .collect();
+        assert_eq!(vec!["m/44'/0'/0'/x/x", "m/86'/0'/0'/x/x", "m/86'/0'/1'/x/x"], accounts);
+    }
+
+    #[test]
+    pub fn next_unused() {
+        let account = DiscoveredAccount {
+            account: AccountHDPath::from_str("m/84'/0'/0'").unwrap(),
+            last_external: 7,
+            last_internal: None,
+        };
+        assert_eq!("m/84'/0'/0'/0/8", account.next_unused(0).unwrap().to_string());
+        assert_eq!("m/84'/0'/0'/1/0", account.next_unused(1).unwrap().to_string());
+        assert_eq!(None, account.next_unused(2));
+    }
+
+    #[test]
+    pub fn stops_on_error() {
+        let discovery = AccountDiscovery::new(0, &[Purpose::Witness]);
+        let failing = |hd_path: &StandardHDPath| {
+            if hd_path.index() < 3 { Ok(true) } else { Err("unavailable") }
+        };
+        assert_eq!(Err(ScanError::Source("unavailable")), discovery.discover(&failing));
+    }
+
+    #[test]
+    pub fn discover_async() {
+        let history = History::new(&["m/84'/0'/0'/0/0", "m/84'/0'/1'/0/2", "m/84'/0'/1'/1/1"]);
+        let discovery = AccountDiscovery::new(0, &[Purpose::Witness]);
+        let expected = discovery.discover(&history).unwrap();
+        let actual = block_on(discovery.discover_async(&history)).unwrap();
+        assert_eq!(2, actual.len());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    pub fn err_on_zero_gap_limit() {
+        let history = History::new(&["m/84'/0'/0'/0/0"]);
+        let mut discovery = AccountDiscovery::new(0, &[Purpose::Witness]);
+        discovery.internal_gap_limit = 0;
+        assert_eq!(Err(ScanError::ZeroGapLimit), discovery.discover(&history));
+        assert_eq!(Err(ScanError::ZeroGapLimit), block_on(discovery.discover_async(&history)));
+        assert!(history.checked.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn skips_exhausted_purpose() {
+        let history = History::new(&["m/44'/0'/0'/0/0", "m/84'/0'/0'/0/0"]);
+        let discovery = AccountDiscovery::new(0, &[Purpose::Pubkey, Purpose::Witness]);
+        let accounts: Vec<String> = discovery.discover(&history).unwrap().iter()
+            .map(|a| a.account.to_string())
+            .collect();
+        assert_eq!(vec!["m/44'/0'/0'/x/x", "m/84'/0'/0'/x/x"], accounts);
+        assert!(AccountDiscovery::new(0, &[]).discover(&history).unwrap().is_empty());
+    }
+
+    fn assert_send<T: Send>(value: T) -> T {
+        value
+    }
+
+    #[test]
+    pub fn discover_async_is_send() {
+        let history = History::new(&["m/84'/0'/0'/0/0"]);
+        let discovery = AccountDiscovery::new(0, &[Purpose::Witness]);
+        let future = assert_send(discovery.discover_async(&history));
+        assert_eq!(1, block_on(future).unwrap().len());
+    }
+}
@@ -6,7 +6,9 @@ pub enum Error {
     InvalidLength(usize),
     InvalidPurpose(u32),
     InvalidStructure,
-    InvalidFormat
+    InvalidFormat,
+    KeyDerivation,
+    InvalidMnemonic,
 }
 
 impl Display for Error {
@@ -16,9 +18,27 @@ impl Display for Error {
             Error::InvalidLength(len) => write!(f, "Invalid length: {}", len),
             Error::InvalidPurpose(purpose) => write!(f, "Invalid purpose: {}", purpose),
             Error::InvalidStructure => write!(f, "Invalid structure"),
-            Error::InvalidFormat => write!(f, "Invalid format")
+            Error::InvalidFormat => write!(f, "Invalid format"),
+            Error::KeyDerivation => write!(f, "Key derivation failed"),
+            Error::InvalidMnemonic => write!(f, "Invalid mnemonic"),
         }
     }
 }
 
-impl std::error::Error for Error {}
\ No newline at end of file
+impl std::error::Error for Error {}
+
+impl From<std::convert::Infallible> for Error {
+    fn from(value: std::convert::Infallible) -> Self {
+        match value {}
+    }
+}
+
+#[cfg(feature = "with-bitcoin")]
+impl From<bitcoin::bip32::Error> for Error {
+    fn from(value: bitcoin::bip32::Error) -> Self {
+        match value {
+            bitcoin::bip32::Error::InvalidChildNumber(_) => Error::HighBitIsSet,
+            _ => Error::KeyDerivation
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{Error, ExtendedPrivateKey, StandardHDPath};
+use bitcoin::secp256k1::{PublicKey, Secp256k1, Signing};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use tiny_keccak::{Hasher, Keccak};
+
+/// Ethereum (and other EVM compatible blockchains) address, which is the last 20 bytes of Keccak-256 hash of the public key.
+///
+/// Displayed with a mixed-case checksum as defined by [EIP-55](https://eips.ethereum.org/EIPS/eip-55), e.g. `0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct EthereumAddress(pub [u8; 20]);
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut output = [0u8; 32];
+    hasher.update(data);
+    hasher.finalize(&mut output);
+    output
+}
+
+impl EthereumAddress {
+
+    /// Address for the public key
+    pub fn from_public_key(key: &PublicKey) -> EthereumAddress {
+        // uncompressed key is 0x04 prefix followed by 64 bytes of X and Y
+        let hash = keccak256(&key.serialize_uncompressed()[1..]);
+        let mut value = [0u8; 20];
+        value.copy_from_slice(&hash[12..]);
+        EthereumAddress(value)
+    }
+
+    /// Check if the string is a valid address. If it's in a mixed case it also verifies the EIP-55 checksum.
+    pub fn is_valid(value: &str) -> bool {
+        EthereumAddress::from_str(value).is_ok()
+    }
+}
+
+impl fmt::Display for EthereumAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex: String = self.0.iter().map(|b| format!("{:02x}", b)).collect();
+        let hash = keccak256(hex.as_bytes());
+        write!(f, "0x")?;
+        for (i, c) in hex.chars().enumerate() {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                write!(f, "{}", c.to_ascii_uppercase())?;
+            } else {
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for EthereumAddress {
+    type Err = Error;
+
+    /// Parse address with `0x` prefix. A mixed case address must have a valid EIP-55 checksum,
+    /// all-lowercase or all-uppercase addresses are accepted without checksum.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix("0x").ok_or(Error::InvalidFormat)?;
+        if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Error::InvalidFormat)
+        }
+        let mut value = [0u8; 20];
+        for (i, b) in value.iter_mut().enumerate() {
+            *b = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| Error::InvalidFormat)?;
+        }
+        let address = EthereumAddress(value);
+        let has_lower = hex.chars().any(|c| c.is_ascii_lowercase());
+        let has_upper = hex.chars().any(|c| c.is_ascii_uppercase());
+        if has_lower && has_upper && address.to_string() != s {
+            return Err(Error::InvalidFormat)
+        }
+        Ok(address)
+    }
+}
+
+impl TryFrom<&str> for EthereumAddress {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        EthereumAddress::from_str(value)
+    }
+}
+
+impl StandardHDPath {
+
+    /// Derive the key for the current path from the master (root) private key and get its Ethereum address.
+    ///
+    /// Usually it's a path with coin type `60'`, like `m/44'/60'/0'/0/0`, but the coin type isn't verified
+    /// because many EVM-compatible chains use their own.
+    ///
+    /// ```
+    /// use hdpath::{Seed, StandardHDPath};
+    /// use bitcoin::secp256k1::Secp256k1;
+    /// use bitcoin::NetworkKind;
+    /// # use std::str::FromStr;
+    ///
+    /// let secp = Secp256k1::new();
+    /// let master = Seed::from_bytes(&[0u8; 64]).unwrap().to_master(NetworkKind::Main).unwrap();
+    /// let hd_path = StandardHDPath::from_str("m/44'/60'/0'/0/0").unwrap();
+    /// let address = hd_path.ethereum_address(&secp, &master).unwrap();
+    /// ```
+    pub fn ethereum_address<C: Signing>(&self, secp: &Secp256k1<C>, master: &ExtendedPrivateKey) -> Result<EthereumAddress, Error> {
+        let key = master.derive(secp, self)?;
+        Ok(EthereumAddress::from_public_key(&key.public_key(secp)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Seed;
+    use bitcoin::NetworkKind;
+
+    // seed for "test test test test test test test test test test test junk"
+    const SEED: &str = "9dfc3c64c2f8bede1533b6a79f8570e5943e0b8fd1cf77107adf7b72cef42185d564a3aee24cab43f80e3c4538087d70fc824eabbad596a23c97b6ee8322ccc0";
+
+    fn master() -> ExtendedPrivateKey {
+        let seed: Vec<u8> = (0..SEED.len()).step_by(2)
+            .map(|i| u8::from_str_radix(&SEED[i..i + 2], 16).unwrap())
+            .collect();
+        Seed::from_bytes(&seed).unwrap().to_master(NetworkKind::Main).unwrap()
+    }
+
+    #[test]
+    pub fn derive_test_mnemonic_addresses() {
+        let secp = Secp256k1::new();
+        let master = master();
+        let expected = [
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+            "0x90F79bf6EB2c4f870365E785982E1f101E93b906",
+        ];
+        for (i, exp) in expected.iter().enumerate() {
+            let hd_path = StandardHDPath::from_str(format!("m/44'/60'/0'/0/{}", i).as_str()).unwrap();
+            let act = hd_path.ethereum_address(&secp, &master).unwrap();
+            assert_eq!(*exp, act.to_string());
+        }
+    }
+
+    #[test]
+    pub fn eip55_checksum() {
+        let addresses = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for address in addresses {
+            let parsed = EthereumAddress::from_str(address.to_lowercase().as_str()).unwrap();
+            assert_eq!(address, parsed.to_string());
+            assert_eq!(parsed, EthereumAddress::from_str(address).unwrap());
+        }
+    }
+
+    #[test]
+    pub fn accept_single_case() {
+        assert!(EthereumAddress::is_valid("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"));
+        assert!(EthereumAddress::is_valid("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED"));
+    }
+
+    #[test]
+    pub fn err_on_invalid_checksum() {
+        assert!(!EthereumAddress::is_valid("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"));
+        assert!(!EthereumAddress::is_valid("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+    }
+
+    #[test]
+    pub fn err_on_invalid_format() {
+        let values = [
+            "", "0x", "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed00",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAé",
+            // `from_str_radix` accepts a sign
+            "0x+a00000000000000000000000000000000000000",
+            "0x-a00000000000000000000000000000000000000",
+        ];
+        for value in values {
+            assert!(EthereumAddress::from_str(value).is_err(), "value: {}", value);
+        }
+    }
+}
@@ -0,0 +1,460 @@
+//! C interface, enabled with `ffi` feature. The header `include/hdpath.h` is generated by cbindgen, and the build
+//! checks that it is the same as the one generated from the current source.
+//!
+//! Memory ownership:
+//! - a path handle returned by `hdpath_parse` or `hdpath_from_bytes` is owned by the caller and must be released
+//!   with `hdpath_free` exactly once;
+//! - strings and byte arrays are written into buffers provided by the caller, the library never returns
+//!   memory allocated for them;
+//! - a message returned by `hdpath_error_message` is a static string and must not be released.
+//!
+//! All functions return `HD_PATH_RESULT_OK` (i.e., `0`) on success, or a negative error code. Output parameters are
+//! modified only on success, except `out_len` for `HD_PATH_RESULT_BUFFER_TOO_SMALL` which is set to the required size.
+//! To get the required size a NULL buffer with zero length can be passed.
+
+use crate::{classify, CustomHDPath, Error, HDPath, PathKind, StandardHDPath};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::str::FromStr;
+use std::{ptr, slice};
+
+/// Result code of a function call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum HDPathResult {
+    Ok = 0,
+    NullPointer = -1,
+    InvalidUtf8 = -2,
+    BufferTooSmall = -3,
+    OutOfBounds = -4,
+    HighBitIsSet = -10,
+    InvalidLength = -11,
+    InvalidPurpose = -12,
+    InvalidStructure = -13,
+    InvalidFormat = -14,
+    KeyDerivation = -15,
+    InvalidMnemonic = -16,
+}
+
+impl HDPathResult {
+    const ALL: [HDPathResult; 12] = [
+        HDPathResult::Ok,
+        HDPathResult::NullPointer,
+        HDPathResult::InvalidUtf8,
+        HDPathResult::BufferTooSmall,
+        HDPathResult::OutOfBounds,
+        HDPathResult::HighBitIsSet,
+        HDPathResult::InvalidLength,
+        HDPathResult::InvalidPurpose,
+        HDPathResult::InvalidStructure,
+        HDPathResult::InvalidFormat,
+        HDPathResult::KeyDerivation,
+        HDPathResult::InvalidMnemonic,
+    ];
+
+    /// Result for an integer code received from C, which may be not a valid value of the enum
+    fn from_code(code: c_int) -> Option<HDPathResult> {
+        HDPathResult::ALL.iter().find(|r| **r as c_int == code).copied()
+    }
+}
+
+/// Kind of a path, see `hdpath::classify`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum HDPathKind {
+    Unknown = 0,
+    Standard = 1,
+    Account = 2,
+    Multisig = 3,
+    MultisigAccount = 4,
+    LedgerLive = 5,
+    Short = 6,
+    Electrum = 7,
+}
+
+impl From<&PathKind> for HDPathKind {
+    fn from(value: &PathKind) -> Self {
+        match value {
+            PathKind::Standard { .. } => HDPathKind::Standard,
+            PathKind::Account { .. } => HDPathKind::Account,
+            PathKind::Multisig { .. } => HDPathKind::Multisig,
+            PathKind::MultisigAccount { .. } => HDPathKind::MultisigAccount,
+            PathKind::LedgerLive { .. } => HDPathKind::LedgerLive,
+            PathKind::Short(_) => HDPathKind::Short,
+            PathKind::Electrum { .. } => HDPathKind::Electrum,
+            PathKind::Unknown => HDPathKind::Unknown,
+        }
+    }
+}
+
+impl From<Error> for HDPathResult {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::HighBitIsSet => HDPathResult::HighBitIsSet,
+            Error::InvalidLength(_) => HDPathResult::InvalidLength,
+            Error::InvalidPurpose(_) => HDPathResult::InvalidPurpose,
+            Error::InvalidStructure => HDPathResult::InvalidStructure,
+            Error::InvalidFormat => HDPathResult::InvalidFormat,
+            Error::KeyDerivation => HDPathResult::KeyDerivation,
+            Error::InvalidMnemonic => HDPathResult::InvalidMnemonic,
+        }
+    }
+}
+
+/// Opaque handle to a parsed HD Path
+pub struct HDPathHandle {
+    path: CustomHDPath,
+}
+
+unsafe fn read_str<'a>(input: *const c_char) -> Result<&'a str, HDPathResult> {
+    if input.is_null() {
+        return Err(HDPathResult::NullPointer)
+    }
+    CStr::from_ptr(input).to_str().map_err(|_| HDPathResult::InvalidUtf8)
+}
+
+unsafe fn write_handle(path: CustomHDPath, out: *mut *mut HDPathHandle) -> HDPathResult {
+    *out = Box::into_raw(Box::new(HDPathHandle { path }));
+    HDPathResult::Ok
+}
+
+/// Accepts NULL `buf` only with zero `buf_len`, i.e. to request the required size
+unsafe fn write_buf(data: &[u8], buf: *mut u8, buf_len: usize, out_len: *mut usize) -> HDPathResult {
+    if out_len.is_null() || (buf.is_null() && buf_len != 0) {
+        return HDPathResult::NullPointer
+    }
+    *out_len = data.len();
+    if buf_len < data.len() {
+        return HDPathResult::BufferTooSmall
+    }
+    ptr::copy_nonoverlapping(data.as_ptr(), buf, data.len());
+    HDPathResult::Ok
+}
+
+/// Parse a NUL-terminated string, like `m/44'/0'/0'/0/0`, into a new handle written to `out`.
+///
+/// # Safety
+/// `input` must be a valid NUL-terminated string and `out` a valid pointer. The handle must be released
+/// with `hdpath_free`.
+#[no_mangle]
+pub unsafe extern "C" fn hdpath_parse(input: *const c_char, out: *mut *mut HDPathHandle) -> HDPathResult {
+    if out.is_null() {
+        return HDPathResult::NullPointer
+    }
+    let input = match read_str(input) {
+        Ok(input) => input,
+        Err(e) => return e
+    };
+    match CustomHDPath::from_str(input) {
+        Ok(path) => write_handle(path, out),
+        Err(e) => e.into()
+    }
+}
+
+/// Decode the `to_bytes` encoding (number of elements following by 4-byte BE values) into a new handle written to `out`.
+///
+/// # Safety
+/// `data` must point to `len` readable bytes and `out` must be a valid pointer. The handle must be released
+/// with `hdpath_free`.
+#[no_mangle]
+pub unsafe extern "C" fn hdpath_from_bytes(data: *const u8, len: usize, out: *mut *mut HDPathHandle) -> HDPathResult {
+    if data.is_null() || out.is_null() {
+        return HDPathResult::NullPointer
+    }
+    match CustomHDPath::from_bytes(slice::from_raw_parts(data, len)) {
+        Ok(path) => write_handle(path, out),
+        Err(e) => e.into()
+    }
+}
+
+/// Release the handle. Does nothing if `path` is NULL.
+///
+/// # Safety
+/// `path` must be a handle created by this library and not released before.
+#[no_mangle]
+pub unsafe extern "C" fn hdpath_free(path: *mut HDPathHandle) {
+    if !path.is_null() {
+        drop(Box::from_raw(path));
+    }
+}
+
+/// Write the number of elements of the path to `out_len`.
+///
+/// # Safety
+/// `path` must be a valid handle and `out_len` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn hdpath_len(path: *const HDPathHandle, out_len: *mut u8) -> HDPathResult {
+    if path.is_null() || out_len.is_null() {
+        return HDPathResult::NullPointer
+    }
+    *out_len = (*path).path.len();
+    HDPathResult::Ok
+}
+
+/// Write the element at `pos` to `out_value` as a raw value, i.e. with `0x80000000` bit set for a hardened value.
+/// Returns `HD_PATH_RESULT_OUT_OF_BOUNDS` if there is no such element.
+///
+/// # Safety
+/// `path` must be a valid handle and `out_value` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn hdpath_get(path: *const HDPathHandle, pos: u8, out_value: *mut u32) -> HDPathResult {
+    if path.is_null() || out_value.is_null() {
+        return HDPathResult::NullPointer
+    }
+    match (*path).path.get(pos) {
+        Some(value) => {
+            *out_value = value.to_raw();
+            HDPathResult::Ok
+        }
+        None => HDPathResult::OutOfBounds
+    }
+}
+
+/// Write the path as a NUL-terminated string, like `m/44'/0'/0'/0/0`, into `buf` of `buf_len` bytes.
+/// The size of the string including NUL is written to `out_len`, and if it's larger than `buf_len` the function
+/// returns `HD_PATH_RESULT_BUFFER_TOO_SMALL`.
+///
+/// # Safety
+/// `path` must be a valid handle, `buf` must point to `buf_len` writable bytes and `out_len` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn hdpath_format(path: *const HDPathHandle, buf: *mut c_char, buf_len: usize, out_len: *mut usize) -> HDPathResult {
+    if path.is_null() {
+        return HDPathResult::NullPointer
+    }
+    let mut value = (*path).path.to_string().into_bytes();
+    value.push(0);
+    write_buf(&value, buf as *mut u8, buf_len, out_len)
+}
+
+/// Write the `to_bytes` encoding of the path into `buf` of `buf_len` bytes. The size of the encoding is written
+/// to `out_len`, and if it's larger than `buf_len` the function returns `HD_PATH_RESULT_BUFFER_TOO_SMALL`.
+///
+/// # Safety
+/// `path` must be a valid handle, `buf` must point to `buf_len` writable bytes and `out_len` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn hdpath_to_bytes(path: *const HDPathHandle, buf: *mut u8, buf_len: usize, out_len: *mut usize) -> HDPathResult {
+    if path.is_null() {
+        return HDPathResult::NullPointer
+    }
+    write_buf(&(*path).path.to_bytes(), buf, buf_len, out_len)
+}
+
+/// Check that the NUL-terminated string is a valid standard path `m/purpose'/coin_type'/account'/change/index`.
+/// Returns `HD_PATH_RESULT_OK` if it is, or the error code otherwise.
+///
+/// # Safety
+/// `input` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn hdpath_validate_standard(input: *const c_char) -> HDPathResult {
+    let input = match read_str(input) {
+        Ok(input) => input,
+        Err(e) => return e
+    };
+    match StandardHDPath::from_str(input) {
+        Ok(_) => HDPathResult::Ok,
+        Err(e) => e.into()
+    }
+}
+
+/// Write the kind of the path (see `HDPathKind`) to `out_kind`.
+///
+/// # Safety
+/// `path` must be a valid handle and `out_kind` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn hdpath_classify(path: *const HDPathHandle, out_kind: *mut HDPathKind) -> HDPathResult {
+    if path.is_null() || out_kind.is_null() {
+        return HDPathResult::NullPointer
+    }
+    *out_kind = HDPathKind::from(&classify(&(*path).path));
+    HDPathResult::Ok
+}
+
+/// Static NUL-terminated description of the result code, or `Unknown error` for an unknown code. Must not be released.
+#[no_mangle]
+pub extern "C" fn hdpath_error_message(code: c_int) -> *const c_char {
+    let code = match HDPathResult::from_code(code) {
+        Some(code) => code,
+        None => return b"Unknown error\0".as_ptr() as *const c_char
+    };
+    let msg: &'static [u8] = match code {
+        HDPathResult::Ok => b"Ok\0",
+        HDPathResult::NullPointer => b"Null pointer\0",
+        HDPathResult::InvalidUtf8 => b"Invalid UTF-8 string\0",
+        HDPathResult::BufferTooSmall => b"Buffer is too small\0",
+        HDPathResult::OutOfBounds => b"Position is out of bounds\0",
+        HDPathResult::HighBitIsSet => b"High bit is set\0",
+        HDPathResult::InvalidLength => b"Invalid length\0",
+        HDPathResult::InvalidPurpose => b"Invalid purpose\0",
+        HDPathResult::InvalidStructure => b"Invalid structure\0",
+        HDPathResult::InvalidFormat => b"Invalid format\0",
+        HDPathResult::KeyDerivation => b"Key derivation failed\0",
+        HDPathResult::InvalidMnemonic => b"Invalid mnemonic\0",
+    };
+    msg.as_ptr() as *const c_char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    fn parse(value: &str) -> Result<*mut HDPathHandle, HDPathResult> {
+        let input = CString::new(value).unwrap();
+        let mut handle: *mut HDPathHandle = ptr::null_mut();
+        let result = unsafe { hdpath_parse(input.as_ptr(), &mut handle) };
+        if result == HDPathResult::Ok {
+            Ok(handle)
+        } else {
+            assert!(handle.is_null());
+            Err(result)
+        }
+    }
+
+    #[test]
+    pub fn parse_and_read() {
+        let handle = parse("m/84'/0'/1'/0/5").unwrap();
+        let mut len = 0u8;
+        assert_eq!(HDPathResult::Ok, unsafe { hdpath_len(handle, &mut len) });
+        assert_eq!(5, len);
+        let mut value = 0u32;
+        assert_eq!(HDPathResult::Ok, unsafe { hdpath_get(handle, 0, &mut value) });
+        assert_eq!(0x80000054, value);
+        assert_eq!(HDPathResult::Ok, unsafe { hdpath_get(handle, 4, &mut value) });
+        assert_eq!(5, value);
+        assert_eq!(HDPathResult::OutOfBounds, unsafe { hdpath_get(handle, 5, &mut value) });
+        assert_eq!(5, value);
+        unsafe { hdpath_free(handle) };
+    }
+
+    #[test]
+    pub fn err_parse() {
+        assert_eq!(Err(HDPathResult::InvalidFormat), parse("m/foo"));
+        assert_eq!(Err(HDPathResult::InvalidFormat), parse("m/2147483648"));
+        let mut handle: *mut HDPathHandle = ptr::null_mut();
+        assert_eq!(HDPathResult::NullPointer, unsafe { hdpath_parse(ptr::null(), &mut handle) });
+        let input = CString::new("m/0").unwrap();
+        assert_eq!(HDPathResult::NullPointer, unsafe { hdpath_parse(input.as_ptr(), ptr::null_mut()) });
+        let invalid = [b'm', b'/', 0xff, 0];
+        assert_eq!(HDPathResult::InvalidUtf8, unsafe { hdpath_parse(invalid.as_ptr() as *const c_char, &mut handle) });
+    }
+
+    #[test]
+    pub fn format() {
+        let handle = parse("M/44H/60H/0H/0/1").unwrap();
+        let mut buf = [0 as c_char; 32];
+        let mut len = 0usize;
+        assert_eq!(HDPathResult::Ok, unsafe { hdpath_format(handle, buf.as_mut_ptr(), buf.len(), &mut len) });
+        assert_eq!(17, len);
+        assert_eq!("m/44'/60'/0'/0/1", unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str().unwrap());
+        unsafe { hdpath_free(handle) };
+    }
+
+    #[test]
+    pub fn format_small_buffer() {
+        let handle = parse("m/44'/60'/0'/0/1").unwrap();
+        let mut buf = [0 as c_char; 8];
+        let mut len = 0usize;
+        assert_eq!(HDPathResult::BufferTooSmall, unsafe { hdpath_format(handle, buf.as_mut_ptr(), buf.len(), &mut len) });
+        assert_eq!(17, len);
+        assert_eq!([0 as c_char; 8], buf);
+        assert_eq!(HDPathResult::BufferTooSmall, unsafe { hdpath_format(handle, ptr::null_mut(), 0, &mut len) });
+        assert_eq!(17, len);
+        unsafe { hdpath_free(handle) };
+    }
+
+    #[test]
+    pub fn err_null_buffer() {
+        let handle = parse("m/44'/60'/0'/0/1").unwrap();
+        let mut len = 0usize;
+        assert_eq!(HDPathResult::NullPointer, unsafe { hdpath_format(handle, ptr::null_mut(), 32, &mut len) });
+        assert_eq!(0, len);
+        assert_eq!(HDPathResult::NullPointer, unsafe { hdpath_to_bytes(handle, ptr::null_mut(), 32, &mut len) });
+        assert_eq!(0, len);
+        let mut buf = [0u8; 32];
+        assert_eq!(HDPathResult::NullPointer, unsafe { hdpath_to_bytes(handle, buf.as_mut_ptr(), buf.len(), ptr::null_mut()) });
+        unsafe { hdpath_free(handle) };
+    }
+
+    #[test]
+    pub fn bytes_roundtrip() {
+        let handle = parse("m/44'/0'/0'").unwrap();
+        let mut buf = [0u8; 16];
+        let mut len = 0usize;
+        assert_eq!(HDPathResult::Ok, unsafe { hdpath_to_bytes(handle, buf.as_mut_ptr(), buf.len(), &mut len) });
+        assert_eq!(13, len);
+        assert_eq!([3, 0x80, 0, 0, 44, 0x80, 0, 0, 0, 0x80, 0, 0, 0], buf[..13]);
+
+        let mut copy: *mut HDPathHandle = ptr::null_mut();
+        assert_eq!(HDPathResult::Ok, unsafe { hdpath_from_bytes(buf.as_ptr(), len, &mut copy) });
+        assert_eq!(unsafe { &(*handle).path }, unsafe { &(*copy).path });
+        unsafe { hdpath_free(handle) };
+        unsafe { hdpath_free(copy) };
+    }
+
+    #[test]
+    pub fn err_from_bytes() {
+        let mut handle: *mut HDPathHandle = ptr::null_mut();
+        let buf = [2u8, 0, 0, 0, 1];
+        assert_eq!(HDPathResult::InvalidFormat, unsafe { hdpath_from_bytes(buf.as_ptr(), buf.len(), &mut handle) });
+        assert!(handle.is_null());
+        assert_eq!(HDPathResult::NullPointer, unsafe { hdpath_from_bytes(ptr::null(), 0, &mut handle) });
+    }
+
+    #[test]
+    pub fn validate_standard() {
+        let check = |value: &str| {
+            let input = CString::new(value).unwrap();
+            unsafe { hdpath_validate_standard(input.as_ptr()) }
+        };
+        assert_eq!(HDPathResult::Ok, check("m/44'/0'/0'/0/0"));
+        assert_eq!(HDPathResult::InvalidLength, check("m/44'/0'/0'"));
+        assert_eq!(HDPathResult::InvalidStructure, check("m/44'/0'/0'/0'/0"));
+        assert_eq!(HDPathResult::InvalidFormat, check("foo"));
+        assert_eq!(HDPathResult::NullPointer, unsafe { hdpath_validate_standard(ptr::null()) });
+    }
+
+    #[test]
+    pub fn free_null() {
+        unsafe { hdpath_free(ptr::null_mut()) };
+    }
+
+    #[test]
+    pub fn classify_paths() {
+        let check = |value: &str| {
+            let handle = parse(value).unwrap();
+            let mut kind = HDPathKind::Unknown;
+            assert_eq!(HDPathResult::Ok, unsafe { hdpath_classify(handle, &mut kind) });
+            unsafe { hdpath_free(handle) };
+            kind
+        };
+        assert_eq!(HDPathKind::Standard, check("m/84'/0'/0'/0/0"));
+        assert_eq!(HDPathKind::Account, check("m/84'/0'/0'"));
+        assert_eq!(HDPathKind::Multisig, check("m/48'/0'/0'/2'/0/1"));
+        assert_eq!(HDPathKind::Electrum, check("m/1/5"));
+        assert_eq!(HDPathKind::Unknown, check("m/5/5/5"));
+        let mut kind = HDPathKind::Unknown;
+        assert_eq!(HDPathResult::NullPointer, unsafe { hdpath_classify(ptr::null(), &mut kind) });
+    }
+
+    #[test]
+    pub fn error_messages() {
+        let msg = unsafe { CStr::from_ptr(hdpath_error_message(HDPathResult::InvalidStructure as c_int)) };
+        assert_eq!("Invalid structure", msg.to_str().unwrap());
+        let msg = unsafe { CStr::from_ptr(hdpath_error_message(0)) };
+        assert_eq!("Ok", msg.to_str().unwrap());
+        let msg = unsafe { CStr::from_ptr(hdpath_error_message(-16)) };
+        assert_eq!("Invalid mnemonic", msg.to_str().unwrap());
+        for code in [1, -5, -17, c_int::MIN, c_int::MAX] {
+            let msg = unsafe { CStr::from_ptr(hdpath_error_message(code)) };
+            assert_eq!("Unknown error", msg.to_str().unwrap());
+        }
+    }
+
+    #[test]
+    pub fn header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/hdpath.h"));
+        // the committed file may have CRLF line endings after a checkout on Windows
+        let committed = include_str!("../include/hdpath.h").replace("\r\n", "\n");
+        assert!(generated == committed, "include/hdpath.h is outdated, copy it from {}/hdpath.h", env!("OUT_DIR"));
+    }
+}
@@ -0,0 +1,409 @@
+use crate::{AccountHDPath, Error, HDPath, PathValue, ScanError, StandardHDPath};
+use crate::discovery::DEFAULT_GAP_LIMIT;
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+
+/// Derives an identifier for an address path for [`PathIndex`](struct.PathIndex.html), e.g. a public key hash, a script
+/// or any other opaque key to look up by.
+///
+/// It's implemented for closures `Fn(&StandardHDPath) -> Result<Vec<u8>, E>`
+pub trait PathDeriver {
+    type Error;
+
+    fn derive(&self, hd_path: &StandardHDPath) -> Result<Vec<u8>, Self::Error>;
+}
+
+impl<F, E> PathDeriver for F where F: Fn(&StandardHDPath) -> Result<Vec<u8>, E> {
+    type Error = E;
+
+    fn derive(&self, hd_path: &StandardHDPath) -> Result<Vec<u8>, Self::Error> {
+        self(hd_path)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Chain {
+    account: AccountHDPath,
+    change: u32,
+    /// Number of derived addresses, i.e. the next index to derive
+    derived: u32,
+    last_used: Option<u32>,
+}
+
+/// Reverse lookup from a derived identifier (a pubkey hash, a script, etc.) to the path of the address.
+///
+/// It tracks chains of addresses (an account and change), and keeps _gap limit_ addresses derived after
+/// the last used address on each chain, i.e. extends the lookahead window as the addresses are marked as used.
+///
+/// # Usage
+/// ```
+/// use hdpath::{AccountHDPath, PathIndex, StandardHDPath};
+/// use std::convert::Infallible;
+/// # use std::str::FromStr;
+///
+/// // in a real app it would be a pubkey hash, script, etc.
+/// let deriver = |hd_path: &StandardHDPath| -> Result<Vec<u8>, Infallible> {
+///     Ok(format!("addr-{}", hd_path.index()).into_bytes())
+/// };
+/// let mut index = PathIndex::new(20);
+/// index.add_chain(&AccountHDPath::from_str("m/84'/0'/0'").unwrap(), 0, &deriver).unwrap();
+/// assert_eq!(20, index.len());
+///
+/// assert_eq!("m/84'/0'/0'/0/15", index.get(b"addr-15").unwrap().to_string());
+/// // received on #15, so now it derives up to #35
+/// index.mark_used(b"addr-15", &deriver).unwrap();
+/// assert_eq!(36, index.len());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PathIndex {
+    gap_limit: u32,
+    chains: Vec<Chain>,
+    entries: BTreeMap<Vec<u8>, StandardHDPath>,
+}
+
+impl Default for PathIndex {
+    fn default() -> Self {
+        PathIndex::new(DEFAULT_GAP_LIMIT)
+    }
+}
+
+impl PathIndex {
+
+    /// Create an empty index which keeps `gap_limit` addresses derived after the last used address
+    pub fn new(gap_limit: u32) -> PathIndex {
+        PathIndex {
+            gap_limit,
+            chains: vec![],
+            entries: BTreeMap::new(),
+        }
+    }
+
+    pub fn gap_limit(&self) -> u32 {
+        self.gap_limit
+    }
+
+    /// Number of the indexed identifiers
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Find path of the identifier
+    pub fn get(&self, id: &[u8]) -> Option<&StandardHDPath> {
+        self.entries.get(id)
+    }
+
+    /// All identifiers with their paths, ordered by the identifier
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &StandardHDPath)> {
+        self.entries.iter().map(|(k, v)| (k.as_slice(), v))
+    }
+
+    /// Add a single identifier, which is not a part of a tracked chain
+    pub fn insert(&mut self, id: Vec<u8>, hd_path: StandardHDPath) {
+        self.entries.insert(id, hd_path);
+    }
+
+    /// Last used index on the chain, or `None` if the chain is not tracked or has no used addresses
+    pub fn last_used(&self, account: &AccountHDPath, change: u32) -> Option<u32> {
+        self.chains.iter()
+            .find(|c| c.account == *account && c.change == change)
+            .and_then(|c| c.last_used)
+    }
+
+    /// Start tracking the chain, and derive the first _gap limit_ addresses on it. If the chain is already tracked it
+    /// only derives the addresses missing in its window, e.g. when the deriver failed on a previous call.
+    /// Fails with `ScanError::InvalidPath` if `change` is not a valid normal value
+    pub fn add_chain<D: PathDeriver>(&mut self, account: &AccountHDPath, change: u32, deriver: &D) -> Result<(), ScanError<D::Error>> {
+        if !PathValue::is_ok(change) {
+            return Err(ScanError::InvalidPath(Error::HighBitIsSet))
+        }
+        let pos = match self.chains.iter().position(|c| c.account == *account && c.change == change) {
+            Some(pos) => pos,
+            None => {
+                self.chains.push(Chain {
+                    account: account.clone(),
+                    change,
+                    derived: 0,
+                    last_used: None,
+                });
+                self.chains.len() - 1
+            }
+        };
+        self.extend(pos, deriver)
+    }
+
+    /// Mark the address with the identifier as used, and extend the lookahead window on its chain.
+    /// Returns the path of the identifier, or `None` if it's unknown.
+    pub fn mark_used<D: PathDeriver>(&mut self, id: &[u8], deriver: &D) -> Result<Option<StandardHDPath>, ScanError<D::Error>> {
+        let hd_path = match self.entries.get(id) {
+            Some(hd_path) => hd_path.clone(),
+            None => return Ok(None)
+        };
+        self.mark_used_path(&hd_path, deriver)?;
+        Ok(Some(hd_path))
+    }
+
+    /// Mark the address as used, and extend the lookahead window on its chain. Does nothing if the chain is not tracked.
+    pub fn mark_used_path<D: PathDeriver>(&mut self, hd_path: &StandardHDPath, deriver: &D) -> Result<(), ScanError<D::Error>> {
+        let account = AccountHDPath::from(hd_path);
+        let pos = self.chains.iter().position(|c| c.account == account && c.change == hd_path.change());
+        if let Some(pos) = pos {
+            let chain = &mut self.chains[pos];
+            if chain.last_used.is_none_or(|last| last < hd_path.index()) {
+                chain.last_used = Some(hd_path.index());
+            }
+            self.extend(pos, deriver)?;
+        }
+        Ok(())
+    }
+
+    /// Derive the addresses up to _gap limit_ after the last used one. On a deriver error the addresses derived so far
+    /// are kept, and the next call continues from the failed one
+    fn extend<D: PathDeriver>(&mut self, pos: usize, deriver: &D) -> Result<(), ScanError<D::Error>> {
+        let chain = &mut self.chains[pos];
+        let target = chain.last_used.map_or(0, |i| i + 1).saturating_add(self.gap_limit);
+        while chain.derived < target {
+            let hd_path = match chain.account.address_at(chain.change, chain.derived) {
+                Ok(hd_path) => hd_path,
+                // reached the max index
+                Err(_) => break
+            };
+            let id = deriver.derive(&hd_path).map_err(ScanError::Source)?;
+            self.entries.insert(id, hd_path);
+            chain.derived += 1;
+        }
+        Ok(())
+    }
+
+    /// Encode the index, including the tracked chains. The paths are encoded with [`HDPath::to_bytes`](trait.HDPath.html#method.to_bytes),
+    /// and numbers are 4-byte BE values:
+    /// - `gap_limit`
+    /// - number of chains, and for each chain: first address path (`/change/0`), number of derived addresses, last used index or `0xffffffff`
+    /// - number of identifiers, and for each: length of the identifier, the identifier, path
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        buf.extend_from_slice(&self.gap_limit.to_be_bytes());
+        buf.extend_from_slice(&(self.chains.len() as u32).to_be_bytes());
+        for chain in &self.chains {
+            let first = chain.account.address_at(chain.change, 0)
+                .expect("chain is created for a valid change");
+            buf.extend_from_slice(&first.to_bytes());
+            buf.extend_from_slice(&chain.derived.to_be_bytes());
+            buf.extend_from_slice(&chain.last_used.unwrap_or(u32::MAX).to_be_bytes());
+        }
+        buf.extend_from_slice(&(self.entries.len() as u32).to_be_bytes());
+        for (id, hd_path) in &self.entries {
+            buf.extend_from_slice(&(id.len() as u32).to_be_bytes());
+            buf.extend_from_slice(id);
+            buf.extend_from_slice(&hd_path.to_bytes());
+        }
+        buf
+    }
+
+    /// Decode the index encoded with [`to_bytes`](#method.to_bytes)
+    pub fn from_bytes(value: &[u8]) -> Result<PathIndex, Error> {
+        let mut reader = Reader(value);
+        let gap_limit = reader.u32()?;
+        let mut chains = vec![];
+        for _ in 0..reader.u32()? {
+            let first = StandardHDPath::from_bytes(reader.take(STANDARD_SIZE)?)?;
+            let derived = reader.u32()?;
+            let last_used = match reader.u32()? {
+                u32::MAX => None,
+                n => Some(n),
+            };
+            let account = AccountHDPath::from(&first);
+            chains.push(Chain { account, change: first.change(), derived, last_used });
+        }
+        let mut entries = BTreeMap::new();
+        for _ in 0..reader.u32()? {
+            let len = reader.u32()? as usize;
+            let id = reader.take(len)?.to_vec();
+            let hd_path = StandardHDPath::from_bytes(reader.take(STANDARD_SIZE)?)?;
+            entries.insert(id, hd_path);
+        }
+        if !reader.0.is_empty() {
+            return Err(Error::InvalidFormat)
+        }
+        Ok(PathIndex { gap_limit, chains, entries })
+    }
+}
+
+/// Size of a StandardHDPath encoded with `to_bytes`
+const STANDARD_SIZE: usize = 1 + 4 * 5;
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < len {
+            return Err(Error::InvalidFormat)
+        }
+        let (value, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(value)
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::convert::Infallible;
+    use std::str::FromStr;
+
+    fn deriver(hd_path: &StandardHDPath) -> Result<Vec<u8>, Infallible> {
+        Ok(vec![hd_path.account() as u8, hd_path.change() as u8, hd_path.index() as u8])
+    }
+
+    fn account(value: &str) -> AccountHDPath {
+        AccountHDPath::from_str(value).unwrap()
+    }
+
+    #[test]
+    pub fn derive_initial_window() {
+        let mut index = PathIndex::new(5);
+        index.add_chain(&account("m/84'/0'/0'"), 0, &deriver).unwrap();
+        index.add_chain(&account("m/84'/0'/0'"), 1, &deriver).unwrap();
+        assert_eq!(10, index.len());
+        assert_eq!("m/84'/0'/0'/0/4", index.get(&[0, 0, 4]).unwrap().to_string());
+        assert_eq!("m/84'/0'/0'/1/0", index.get(&[0, 1, 0]).unwrap().to_string());
+        assert_eq!(None, index.get(&[0, 0, 5]));
+        // same chain again
+        index.add_chain(&account("m/84'/0'/0'"), 0, &deriver).unwrap();
+        assert_eq!(10, index.len());
+    }
+
+    #[test]
+    pub fn err_on_invalid_change() {
+        let mut index = PathIndex::new(5);
+        assert_eq!(
+            Err(ScanError::InvalidPath(Error::HighBitIsSet)),
+            index.add_chain(&account("m/84'/0'/0'"), 0x80000000, &deriver)
+        );
+        assert!(index.is_empty());
+        // still can be encoded
+        assert_eq!(index, PathIndex::from_bytes(&index.to_bytes()).unwrap());
+    }
+
+    #[test]
+    pub fn extend_on_use() {
+        let mut index = PathIndex::new(5);
+        let acc = account("m/84'/0'/1'");
+        index.add_chain(&acc, 0, &deriver).unwrap();
+        assert_eq!(None, index.last_used(&acc, 0));
+
+        let used = index.mark_used(&[1, 0, 3], &deriver).unwrap();
+        assert_eq!(Some(StandardHDPath::from_str("m/84'/0'/1'/0/3").unwrap()), used);
+        assert_eq!(Some(3), index.last_used(&acc, 0));
+        assert_eq!(9, index.len());
+        assert!(index.get(&[1, 0, 8]).is_some());
+        assert!(index.get(&[1, 0, 9]).is_none());
+
+        // earlier address doesn't change the window
+        index.mark_used(&[1, 0, 1], &deriver).unwrap();
+        assert_eq!(Some(3), index.last_used(&acc, 0));
+        assert_eq!(9, index.len());
+
+        assert_eq!(None, index.mark_used(&[9, 9, 9], &deriver).unwrap());
+    }
+
+    #[test]
+    pub fn untracked_paths() {
+        let mut index = PathIndex::default();
+        assert_eq!(20, index.gap_limit());
+        assert!(index.is_empty());
+        let hd_path = StandardHDPath::from_str("m/44'/0'/0'/0/100").unwrap();
+        index.insert(b"manual".to_vec(), hd_path.clone());
+        assert_eq!(Some(&hd_path), index.get(b"manual"));
+        assert_eq!(Some(hd_path), index.mark_used(b"manual", &deriver).unwrap());
+        assert_eq!(1, index.len());
+    }
+
+    #[test]
+    pub fn deriver_error() {
+        let mut index = PathIndex::new(5);
+        let failing = |_: &StandardHDPath| -> Result<Vec<u8>, &str> { Err("no key") };
+        assert_eq!(Err(ScanError::Source("no key")), index.add_chain(&account("m/84'/0'/0'"), 0, &failing));
+    }
+
+    #[test]
+    pub fn recover_after_deriver_error() {
+        let mut index = PathIndex::new(5);
+        let acc = account("m/84'/0'/0'");
+        // fails on the address #3 once, then works
+        let failed = Cell::new(false);
+        let flaky = |hd_path: &StandardHDPath| -> Result<Vec<u8>, &str> {
+            if hd_path.index() == 3 && !failed.replace(true) {
+                return Err("temporary")
+            }
+            Ok(deriver(hd_path).unwrap())
+        };
+        assert_eq!(Err(ScanError::Source("temporary")), index.add_chain(&acc, 0, &flaky));
+        assert_eq!(3, index.len());
+        index.add_chain(&acc, 0, &flaky).unwrap();
+        assert_eq!(5, index.len());
+        assert_eq!("m/84'/0'/0'/0/3", index.get(&[0, 0, 3]).unwrap().to_string());
+        assert_eq!("m/84'/0'/0'/0/4", index.get(&[0, 0, 4]).unwrap().to_string());
+        // and extends the window from there
+        index.mark_used(&[0, 0, 4], &flaky).unwrap();
+        assert_eq!(10, index.len());
+    }
+
+    #[test]
+    pub fn mark_used_error() {
+        let mut index = PathIndex::new(5);
+        index.add_chain(&account("m/84'/0'/0'"), 0, &deriver).unwrap();
+        let failing = |_: &StandardHDPath| -> Result<Vec<u8>, &str> { Err("no key") };
+        assert_eq!(Err(ScanError::Source("no key")), index.mark_used(&[0, 0, 2], &failing));
+    }
+
+    #[test]
+    pub fn encode_and_decode() {
+        let mut index = PathIndex::new(3);
+        index.add_chain(&account("m/84'/0'/0'"), 0, &deriver).unwrap();
+        index.add_chain(&account("m/44'/60'/2'"), 1, &deriver).unwrap();
+        index.mark_used(&[0, 0, 1], &deriver).unwrap();
+        index.insert(vec![], StandardHDPath::from_str("m/49'/0'/0'/0/0").unwrap());
+
+        let bytes = index.to_bytes();
+        let decoded = PathIndex::from_bytes(&bytes).unwrap();
+        assert_eq!(index, decoded);
+        assert_eq!(bytes, decoded.to_bytes());
+    }
+
+    #[test]
+    pub fn encoding_layout() {
+        let mut index = PathIndex::new(1);
+        index.add_chain(&account("m/84'/0'/0'"), 0, &deriver).unwrap();
+        let bytes = index.to_bytes();
+        let path = StandardHDPath::from_str("m/84'/0'/0'/0/0").unwrap().to_bytes();
+        let mut expected = vec![0, 0, 0, 1, 0, 0, 0, 1];
+        expected.extend_from_slice(&path);
+        expected.extend_from_slice(&[0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0]);
+        expected.extend_from_slice(&path);
+        assert_eq!(expected, bytes);
+    }
+
+    #[test]
+    pub fn decode_invalid() {
+        let mut index = PathIndex::new(3);
+        index.add_chain(&account("m/84'/0'/0'"), 0, &deriver).unwrap();
+        let bytes = index.to_bytes();
+        assert!(PathIndex::from_bytes(&[]).is_err());
+        assert!(PathIndex::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert!(PathIndex::from_bytes(&extra).is_err());
+        let mut invalid = bytes.clone();
+        invalid[8] = 4;
+        assert!(PathIndex::from_bytes(&invalid).is_err());
+    }
+}
@@ -0,0 +1,298 @@
+//! Historical path layouts used by the Bitcoin wallets before (or in parallel with) BIP-44/49/84, which are needed to
+//! import a seed created by such wallet.
+//!
+//! ```
+//! use hdpath::legacy::{self, LegacyLayout};
+//! # use std::str::FromStr;
+//! # use hdpath::CustomHDPath;
+//!
+//! // m/0'/0/5
+//! let hd_path = legacy::electrum_2fa(0, 5).unwrap();
+//! assert_eq!(vec![LegacyLayout::ElectrumTwoFactor], LegacyLayout::recognize(&hd_path));
+//!
+//! // all the paths to check when the wallet that created the seed is unknown
+//! let candidates = legacy::scan_paths(20).unwrap();
+//! assert_eq!(CustomHDPath::from_str("m/0/0").unwrap(), candidates[0]);
+//! ```
+
+use crate::{classify, AccountHDPath, CustomHDPath, Error, HDPath, PathKind, PathValue, Purpose, ShortHDPath, StandardHDPath};
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+/// Known historical path layout
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum LegacyLayout {
+    /// Electrum standard wallet, `m/change/index`
+    Electrum,
+    /// Electrum 2FA wallet, `m/0'/change/index`
+    ElectrumTwoFactor,
+    /// Early Ledger Bitcoin app, `m/44'/0'/account'/index`, i.e. without the change element
+    LedgerLegacy,
+    /// Mycelium HD accounts, `m/44'/0'/account'/change/index`. Note that Mycelium uses the same keys for
+    /// all the types of addresses (P2PKH, P2SH-P2WPKH and P2WPKH).
+    Mycelium,
+    /// Coinomi, `m/44'/0'/account'/change/index`. Note that Coinomi used BIP-44 paths for SegWit addresses too.
+    Coinomi,
+}
+
+impl LegacyLayout {
+    /// All layouts in the order they should be checked on import
+    pub const ALL: [LegacyLayout; 5] = [
+        LegacyLayout::Electrum,
+        LegacyLayout::ElectrumTwoFactor,
+        LegacyLayout::LedgerLegacy,
+        LegacyLayout::Mycelium,
+        LegacyLayout::Coinomi,
+    ];
+
+    /// Name of the wallet
+    pub fn name(&self) -> &'static str {
+        match self {
+            LegacyLayout::Electrum => "Electrum",
+            LegacyLayout::ElectrumTwoFactor => "Electrum 2FA",
+            LegacyLayout::LedgerLegacy => "Ledger (legacy)",
+            LegacyLayout::Mycelium => "Mycelium",
+            LegacyLayout::Coinomi => "Coinomi",
+        }
+    }
+
+    /// Change values used by the layout, the first is the receive chain
+    pub fn chains(&self) -> &'static [u32] {
+        match self {
+            LegacyLayout::LedgerLegacy => &[0],
+            _ => &[0, 1],
+        }
+    }
+
+    /// Check if the layout has accounts. If not, only account `0` is allowed for `path`
+    pub fn has_accounts(&self) -> bool {
+        matches!(self, LegacyLayout::LedgerLegacy | LegacyLayout::Mycelium | LegacyLayout::Coinomi)
+    }
+
+    /// Path of the address in this layout.
+    /// Returns `Error::InvalidStructure` if the layout doesn't support the account or the change value,
+    /// or `Error::HighBitIsSet` if a value is out of range.
+    pub fn path(&self, account: u32, change: u32, index: u32) -> Result<CustomHDPath, Error> {
+        if account != 0 && !self.has_accounts() {
+            return Err(Error::InvalidStructure)
+        }
+        let hd_path = match self {
+            LegacyLayout::Electrum => electrum(change, index)?,
+            LegacyLayout::ElectrumTwoFactor => electrum_2fa(change, index)?,
+            LegacyLayout::LedgerLegacy => {
+                if change != 0 {
+                    return Err(Error::InvalidStructure)
+                }
+                ledger_legacy(account, index)?.as_custom()
+            }
+            LegacyLayout::Mycelium => mycelium(account, change, index)?.as_custom(),
+            LegacyLayout::Coinomi => coinomi(account, change, index)?.as_custom(),
+        };
+        Ok(hd_path)
+    }
+
+    /// Account, change and index of the path, if it follows the layout
+    pub fn parse(&self, hd_path: &dyn HDPath) -> Option<(u32, u32, u32)> {
+        match self {
+            LegacyLayout::Electrum => match classify(hd_path) {
+                PathKind::Electrum { change, index } => Some((0, change, index)),
+                _ => None
+            },
+            LegacyLayout::ElectrumTwoFactor => match (hd_path.len(), hd_path.get(0), hd_path.get(1), hd_path.get(2)) {
+                (3, Some(PathValue::Hardened(0)), Some(PathValue::Normal(change)), Some(PathValue::Normal(index))) if change <= 1 =>
+                    Some((0, change, index)),
+                _ => None
+            },
+            LegacyLayout::LedgerLegacy => {
+                let hd_path = ShortHDPath::try_from(hd_path.as_custom()).ok()?;
+                if hd_path.purpose != Purpose::Pubkey || hd_path.coin_type != 0 {
+                    return None
+                }
+                Some((hd_path.account, 0, hd_path.index))
+            }
+            LegacyLayout::Mycelium | LegacyLayout::Coinomi => {
+                let hd_path = StandardHDPath::try_from(hd_path.as_custom()).ok()?;
+                if *hd_path.purpose() != Purpose::Pubkey || hd_path.coin_type() != 0 || hd_path.change() > 1 {
+                    return None
+                }
+                Some((hd_path.account(), hd_path.change(), hd_path.index()))
+            }
+        }
+    }
+
+    /// Check if the path follows the layout
+    pub fn matches(&self, hd_path: &dyn HDPath) -> bool {
+        self.parse(hd_path).is_some()
+    }
+
+    /// All layouts matching the path. A path may belong to several layouts, ex. Mycelium and Coinomi use the same one.
+    pub fn recognize(hd_path: &dyn HDPath) -> Vec<LegacyLayout> {
+        LegacyLayout::ALL.iter().filter(|l| l.matches(hd_path)).cloned().collect()
+    }
+}
+
+/// Electrum path `m/change/index`, where change is `0` for receive and `1` for change addresses
+pub fn electrum(change: u32, index: u32) -> Result<CustomHDPath, Error> {
+    if change > 1 {
+        return Err(Error::InvalidStructure)
+    }
+    if !PathValue::is_ok(index) {
+        return Err(Error::HighBitIsSet)
+    }
+    Ok(CustomHDPath(vec![PathValue::Normal(change), PathValue::Normal(index)]))
+}
+
+/// Electrum 2FA path `m/0'/change/index`
+pub fn electrum_2fa(change: u32, index: u32) -> Result<CustomHDPath, Error> {
+    if change > 1 {
+        return Err(Error::InvalidStructure)
+    }
+    if !PathValue::is_ok(index) {
+        return Err(Error::HighBitIsSet)
+    }
+    Ok(CustomHDPath(vec![PathValue::Hardened(0), PathValue::Normal(change), PathValue::Normal(index)]))
+}
+
+/// Early Ledger Bitcoin path `m/44'/0'/account'/index`
+pub fn ledger_legacy(account: u32, index: u32) -> Result<ShortHDPath, Error> {
+    if !PathValue::is_ok(account) || !PathValue::is_ok(index) {
+        return Err(Error::HighBitIsSet)
+    }
+    Ok(ShortHDPath { purpose: Purpose::Pubkey, coin_type: 0, account, index })
+}
+
+/// Mycelium path `m/44'/0'/account'/change/index`
+pub fn mycelium(account: u32, change: u32, index: u32) -> Result<StandardHDPath, Error> {
+    bip44_address(account, change, index)
+}
+
+/// Coinomi Bitcoin path `m/44'/0'/account'/change/index`
+pub fn coinomi(account: u32, change: u32, index: u32) -> Result<StandardHDPath, Error> {
+    bip44_address(account, change, index)
+}
+
+fn bip44_address(account: u32, change: u32, index: u32) -> Result<StandardHDPath, Error> {
+    if change > 1 {
+        return Err(Error::InvalidStructure)
+    }
+    AccountHDPath::try_new(Purpose::Pubkey, 0, account)
+        .and_then(|acc| acc.address_at(change, index))
+        .map_err(|_| Error::HighBitIsSet)
+}
+
+/// Largest `limit` accepted by [`scan_paths`], way above any gap limit used by a real wallet
+pub const MAX_SCAN_LIMIT: u32 = 10_000;
+
+/// Ordered list of the candidate paths to scan for a seed with an unknown origin, with `limit` addresses on each
+/// chain of the first account of each layout. Paths shared by several layouts are included only once.
+///
+/// Returns `Error::InvalidLength` if `limit` is larger than [`MAX_SCAN_LIMIT`], since the whole list is allocated at once.
+pub fn scan_paths(limit: u32) -> Result<Vec<CustomHDPath>, Error> {
+    if limit > MAX_SCAN_LIMIT {
+        return Err(Error::InvalidLength(limit as usize))
+    }
+    let mut seen: HashSet<CustomHDPath> = HashSet::new();
+    let mut result: Vec<CustomHDPath> = Vec::new();
+    for layout in LegacyLayout::ALL.iter() {
+        for change in layout.chains() {
+            for index in 0..limit {
+                let hd_path = layout.path(0, *change, index)?;
+                if seen.insert(hd_path.clone()) {
+                    result.push(hd_path);
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn path(value: &str) -> CustomHDPath {
+        CustomHDPath::from_str(value).unwrap()
+    }
+
+    #[test]
+    pub fn create_paths() {
+        assert_eq!(path("m/0/3"), electrum(0, 3).unwrap());
+        assert_eq!(path("m/1/0"), electrum(1, 0).unwrap());
+        assert_eq!(path("m/0'/1/2"), electrum_2fa(1, 2).unwrap());
+        assert_eq!("m/44'/0'/0'/7", ledger_legacy(0, 7).unwrap().to_string());
+        assert_eq!("m/44'/0'/1'/0/4", mycelium(1, 0, 4).unwrap().to_string());
+        assert_eq!("m/44'/0'/0'/1/4", coinomi(0, 1, 4).unwrap().to_string());
+    }
+
+    #[test]
+    pub fn err_create_invalid() {
+        assert_eq!(Err(Error::InvalidStructure), electrum(2, 0));
+        assert_eq!(Err(Error::HighBitIsSet), electrum(0, 0x80000000));
+        assert_eq!(Err(Error::InvalidStructure), electrum_2fa(2, 0));
+        assert_eq!(Err(Error::HighBitIsSet), electrum_2fa(0, 0x80000000));
+        assert_eq!(Err(Error::HighBitIsSet), ledger_legacy(0x80000000, 0));
+        assert_eq!(Err(Error::InvalidStructure), mycelium(0, 2, 0));
+        assert_eq!(Err(Error::HighBitIsSet), coinomi(0, 0, 0x80000000));
+        assert_eq!(Err(Error::InvalidStructure), LegacyLayout::Electrum.path(1, 0, 0));
+        assert_eq!(Err(Error::InvalidStructure), LegacyLayout::LedgerLegacy.path(0, 1, 0));
+    }
+
+    #[test]
+    pub fn layout_path() {
+        for layout in LegacyLayout::ALL.iter() {
+            for change in layout.chains() {
+                let hd_path = layout.path(0, *change, 5).unwrap();
+                assert_eq!(Some((0, *change, 5)), layout.parse(&hd_path), "{:?}", layout);
+            }
+        }
+        assert_eq!(path("m/44'/0'/2'/9"), LegacyLayout::LedgerLegacy.path(2, 0, 9).unwrap());
+    }
+
+    #[test]
+    pub fn recognize_paths() {
+        assert_eq!(vec![LegacyLayout::Electrum], LegacyLayout::recognize(&path("m/1/5")));
+        assert_eq!(vec![LegacyLayout::ElectrumTwoFactor], LegacyLayout::recognize(&path("m/0'/0/5")));
+        assert_eq!(vec![LegacyLayout::LedgerLegacy], LegacyLayout::recognize(&path("m/44'/0'/0'/5")));
+        assert_eq!(
+            vec![LegacyLayout::Mycelium, LegacyLayout::Coinomi],
+            LegacyLayout::recognize(&StandardHDPath::from_str("m/44'/0'/0'/0/5").unwrap())
+        );
+        assert!(LegacyLayout::recognize(&path("m/2/5")).is_empty());
+        assert!(LegacyLayout::recognize(&path("m/1'/0/5")).is_empty());
+        assert!(LegacyLayout::recognize(&path("m/84'/0'/0'/0/5")).is_empty());
+        assert!(LegacyLayout::recognize(&path("m/44'/60'/0'/0/5")).is_empty());
+        assert!(LegacyLayout::recognize(&path("m/44'/0'/0'/2/5")).is_empty());
+    }
+
+    #[test]
+    pub fn scan_order() {
+        let paths = scan_paths(2).unwrap();
+        let expected: Vec<CustomHDPath> = vec![
+            "m/0/0", "m/0/1", "m/1/0", "m/1/1",
+            "m/0'/0/0", "m/0'/0/1", "m/0'/1/0", "m/0'/1/1",
+            "m/44'/0'/0'/0", "m/44'/0'/0'/1",
+            "m/44'/0'/0'/0/0", "m/44'/0'/0'/0/1", "m/44'/0'/0'/1/0", "m/44'/0'/0'/1/1",
+        ].into_iter().map(path).collect();
+        assert_eq!(expected, paths);
+    }
+
+    #[test]
+    pub fn scan_empty() {
+        assert!(scan_paths(0).unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn scan_max_limit() {
+        let paths = scan_paths(MAX_SCAN_LIMIT).unwrap();
+        assert!(paths.len() > MAX_SCAN_LIMIT as usize);
+        assert_eq!(path("m/44'/0'/0'/1/9999"), paths[paths.len() - 1]);
+    }
+
+    #[test]
+    pub fn err_scan_too_large() {
+        assert_eq!(Err(Error::InvalidLength(10_001)), scan_paths(MAX_SCAN_LIMIT + 1));
+        assert_eq!(Err(Error::InvalidLength(0x8000_0000)), scan_paths(0x8000_0000));
+        assert_eq!(Err(Error::InvalidLength(u32::MAX as usize)), scan_paths(u32::MAX));
+    }
+}
@@ -15,6 +15,10 @@
 //! - [BIP-44](https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki)
 //! - [BIP-49](https://github.com/bitcoin/bips/blob/master/bip-0049.mediawiki)
 //! - [BIP-84](https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki)
+//! - [BIP-86](https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki)
+//! - [BIP-85](https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki)
+//! - [CIP-1852](https://cips.cardano.org/cip/CIP-1852) (Cardano)
+//! - [SLIP-13](https://github.com/satoshilabs/slips/blob/master/slip-0013.md) (with `identity` feature)
 //!
 //! Base traits is [HDPath](trait.HDPath.html), with few specific implementations and general [`CustomHDPath`](struct.CustomHDPath.html)
 //!
@@ -85,23 +89,102 @@
 //! }
 //! ```
 //!
+// lints in the tests which are older than the clippy rules
+
 extern crate byteorder;
 #[cfg(feature = "with-bitcoin")]
 extern crate bitcoin;
+#[cfg(feature = "with-bitcoin")]
+extern crate zeroize;
+#[cfg(feature = "with-bitcoin")]
+extern crate subtle;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+#[cfg(feature = "ethereum")]
+extern crate tiny_keccak;
+#[cfg(feature = "bip39")]
+extern crate bip39;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "substrate")]
+extern crate blake2;
+#[cfg(feature = "identity")]
+extern crate sha2;
 
 mod errors;
 mod traits;
 mod path_account;
+mod path_cardano;
 mod path_custom;
+mod path_lightning;
 mod path_short;
 mod path_standard;
 mod path_value;
 mod purpose;
+mod parser;
+mod classify;
+mod describe;
+mod lint;
+mod policy;
+mod discovery;
+mod index;
+mod path_map;
+mod substrate;
+mod bip85;
+mod scheme;
+pub mod legacy;
+#[doc(hidden)]
+pub mod literal;
+#[cfg(feature = "with-bitcoin")]
+mod derive;
+#[cfg(feature = "with-bitcoin")]
+mod secret;
+#[cfg(feature = "with-bitcoin")]
+mod psbt;
+#[cfg(feature = "address")]
+mod address;
+#[cfg(feature = "ethereum")]
+mod ethereum;
+#[cfg(feature = "bip39")]
+mod mnemonic;
+#[cfg(feature = "identity")]
+mod path_identity;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub use errors::Error;
-pub use traits::HDPath;
+pub use traits::{HDPath, LEDGER_MAX_DEPTH};
 pub use path_account::AccountHDPath;
+pub use path_cardano::{CardanoHDPath, Role};
 pub use path_custom::CustomHDPath;
+pub use path_lightning::{KeyFamily, LightningKeyPath};
+pub use path_short::ShortHDPath;
 pub use path_standard::StandardHDPath;
 pub use path_value::{PathValue};
 pub use purpose::Purpose;
+pub use parser::ParseOptions;
+pub use classify::{classify, PathKind, ScriptType};
+pub use describe::{coin_name, describe, DescriptionFormat};
+pub use lint::{lint, lint_with, LintConfig, LintRule, Severity, TargetNetwork, Warning, WarningCode};
+pub use policy::{ElementPattern, PathPolicy, PathTemplate, PolicyReport, Verdict};
+pub use discovery::{AccountDiscovery, AsyncHistoryOracle, DiscoveredAccount, HistoryOracle, ScanError};
+pub use index::{PathDeriver, PathIndex};
+pub use path_map::{PathMap, PathSet};
+pub use substrate::{Junction, JunctionId, SubstrateDerivationPath};
+pub use bip85::{Application, Bip85Path, MnemonicLanguage};
+pub use scheme::{AddressEncoding, Layout, Scheme, Slot};
+pub use legacy::LegacyLayout;
+#[cfg(feature = "with-bitcoin")]
+pub use derive::AccountKeys;
+#[cfg(feature = "ethereum")]
+pub use ethereum::EthereumAddress;
+#[cfg(feature = "with-bitcoin")]
+pub use secret::{Seed, ExtendedPrivateKey};
+#[cfg(feature = "with-bitcoin")]
+pub use psbt::{PsbtKeyPath, PsbtLocation, PsbtPublicKey};
+#[cfg(feature = "bip39")]
+pub use bip39::Language;
+#[cfg(feature = "identity")]
+pub use path_identity::{Identity, IdentityHDPath};
@@ -0,0 +1,398 @@
+use crate::{AccountHDPath, CustomHDPath, Error, HDPath, PathValue, Purpose, StandardHDPath};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Severity of a lint [`Warning`](struct.Warning.html)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Severity {
+    /// Non-standard, but may be intended
+    Info,
+    /// Likely a mistake, e.g. a wallet would not find the address when scanning
+    Warning,
+    /// Unsafe or invalid for the standard, e.g. may expose private keys
+    Error,
+}
+
+/// Kind of a lint [`Warning`](struct.Warning.html). Each kind has a stable code, returned by `code()`, which is never
+/// reused for another kind.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum WarningCode {
+    /// `HD001` purpose is not hardened
+    NormalPurpose,
+    /// `HD002` coin type is not hardened
+    NormalCoinType,
+    /// `HD003` account is not hardened, i.e. an account xpub and any child private key expose the account private key
+    NormalAccount,
+    /// `HD004` change is hardened
+    HardenedChange,
+    /// `HD005` address index is hardened
+    HardenedIndex,
+    /// `HD006` change is neither `0` (receive) nor `1` (change)
+    UnusualChange,
+    /// `HD007` SegWit purpose (49', 84' or 86') used with a coin that doesn't support SegWit
+    SegwitUnsupported,
+    /// `HD008` address index is larger than `LintConfig::max_index`
+    LargeIndex,
+    /// `HD009` account is larger than `LintConfig::max_account`
+    LargeAccount,
+    /// `HD010` mainnet coin type used when a testnet is expected
+    MainnetCoinOnTestnet,
+    /// `HD011` testnet coin type (`1'`) used when a mainnet is expected
+    TestnetCoinOnMainnet,
+    /// `HD012` path has more elements than `m/purpose'/coin_type'/account'/change/index`
+    TooLong,
+    /// Warning produced by a custom [`LintRule`](trait.LintRule.html), with its own code
+    Custom(&'static str),
+}
+
+impl WarningCode {
+    /// Stable code of the warning, like `HD004`
+    pub fn code(&self) -> &'static str {
+        match self {
+            WarningCode::NormalPurpose => "HD001",
+            WarningCode::NormalCoinType => "HD002",
+            WarningCode::NormalAccount => "HD003",
+            WarningCode::HardenedChange => "HD004",
+            WarningCode::HardenedIndex => "HD005",
+            WarningCode::UnusualChange => "HD006",
+            WarningCode::SegwitUnsupported => "HD007",
+            WarningCode::LargeIndex => "HD008",
+            WarningCode::LargeAccount => "HD009",
+            WarningCode::MainnetCoinOnTestnet => "HD010",
+            WarningCode::TestnetCoinOnMainnet => "HD011",
+            WarningCode::TooLong => "HD012",
+            WarningCode::Custom(code) => code,
+        }
+    }
+}
+
+/// A problem found by [`lint`](fn.lint.html)
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Warning {
+    pub code: WarningCode,
+    pub severity: Severity,
+    /// Position of the path element the warning is about, if it's about a particular element
+    pub position: Option<usize>,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(code: WarningCode, severity: Severity, position: Option<usize>, message: String) -> Warning {
+        Warning { code, severity, position, message }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{} {}: {}", self.code.code(), severity, self.message)
+    }
+}
+
+/// Network the paths are expected to be used on
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TargetNetwork {
+    /// Don't check the coin type for the network
+    Any,
+    Mainnet,
+    Testnet,
+}
+
+/// Options for [`lint`](fn.lint.html)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LintConfig {
+    /// Network the paths are used on. Default is `Any`
+    pub network: TargetNetwork,
+    /// Max address index a wallet would reasonably reach with gap limit scanning. Default is `10000`
+    pub max_index: u32,
+    /// Max account a wallet would reasonably reach with account discovery. Default is `1000`
+    pub max_account: u32,
+    /// Codes to skip
+    pub ignore: Vec<WarningCode>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        LintConfig {
+            network: TargetNetwork::Any,
+            max_index: 10_000,
+            max_account: 1_000,
+            ignore: vec![],
+        }
+    }
+}
+
+/// Custom check to run in addition to the standard ones, see [`lint_with`](fn.lint_with.html)
+pub trait LintRule {
+    fn check(&self, hd_path: &dyn HDPath, config: &LintConfig) -> Vec<Warning>;
+}
+
+/// Coin types known to not support SegWit (see SLIP-44)
+const NON_SEGWIT_COINS: &[u32] = &[3, 5, 60, 61, 118, 133, 144, 145, 195, 354, 501, 1815];
+
+/// Check the path for common mistakes and non-standard values. The BIP-44 structure checks
+/// (`m/purpose'/coin_type'/account'/change/index`) apply only to paths with a BIP-44, 49, 84 or 86 purpose,
+/// hardened or not.
+///
+/// # Usage
+/// ```
+/// use hdpath::{lint, CustomHDPath, LintConfig, Severity, WarningCode};
+/// # use std::str::FromStr;
+///
+/// let hd_path = CustomHDPath::from_str("m/84'/0'/0'/0'/0").unwrap();
+/// let warnings = lint(&hd_path, &LintConfig::default());
+/// assert_eq!(1, warnings.len());
+/// assert_eq!(WarningCode::HardenedChange, warnings[0].code);
+/// assert_eq!(Severity::Error, warnings[0].severity);
+/// assert_eq!("HD004", warnings[0].code.code());
+/// ```
+pub fn lint(hd_path: &dyn HDPath, config: &LintConfig) -> Vec<Warning> {
+    lint_with(hd_path, config, &[])
+}
+
+/// Same as [`lint`](fn.lint.html), but runs the additional `rules` after the standard checks
+pub fn lint_with(hd_path: &dyn HDPath, config: &LintConfig, rules: &[&dyn LintRule]) -> Vec<Warning> {
+    let mut warnings = check_structure(&hd_path.as_custom(), config);
+    for rule in rules {
+        warnings.extend(rule.check(hd_path, config));
+    }
+    warnings.retain(|w| !config.ignore.contains(&w.code));
+    warnings
+}
+
+fn check_structure(path: &CustomHDPath, config: &LintConfig) -> Vec<Warning> {
+    let mut warnings = vec![];
+    let values = &path.0;
+    let purpose = match values.first().map(|p| Purpose::try_from(p.as_number())) {
+        Some(Ok(Purpose::Pubkey)) | Some(Ok(Purpose::ScriptHash)) | Some(Ok(Purpose::Witness)) | Some(Ok(Purpose::Taproot)) => {
+            values[0].as_number()
+        },
+        _ => return warnings
+    };
+
+    match parse_standard(values) {
+        Ok(()) => {}
+        Err(Error::InvalidLength(len)) => {
+            warnings.extend(check_hardened(values));
+            warnings.push(Warning::new(WarningCode::TooLong, Severity::Warning, Some(5),
+                                       format!("Path has {} elements, but purpose {}' allows at most 5", len, purpose)));
+        }
+        Err(_) => warnings.extend(check_hardened(values)),
+    }
+
+    if let Some(coin_type) = values.get(1).map(|v| v.as_number()) {
+        if purpose != 44 && NON_SEGWIT_COINS.contains(&coin_type) {
+            warnings.push(Warning::new(WarningCode::SegwitUnsupported, Severity::Error, Some(0),
+                                       format!("Coin type {}' doesn't support SegWit required by purpose {}'", coin_type, purpose)));
+        }
+        match config.network {
+            TargetNetwork::Testnet if coin_type != 1 => {
+                warnings.push(Warning::new(WarningCode::MainnetCoinOnTestnet, Severity::Error, Some(1),
+                                           format!("Coin type {}' is used on testnet, expected 1'", coin_type)));
+            }
+            TargetNetwork::Mainnet if coin_type == 1 => {
+                warnings.push(Warning::new(WarningCode::TestnetCoinOnMainnet, Severity::Error, Some(1),
+                                           "Testnet coin type 1' is used on mainnet".to_string()));
+            }
+            _ => {}
+        }
+    }
+    if let Some(account) = values.get(2).map(|v| v.as_number()) {
+        if account > config.max_account {
+            warnings.push(Warning::new(WarningCode::LargeAccount, Severity::Warning, Some(2),
+                                       format!("Account {} is beyond the discovery limit {}", account, config.max_account)));
+        }
+    }
+    if let Some(change) = values.get(3).map(|v| v.as_number()) {
+        if change > 1 {
+            warnings.push(Warning::new(WarningCode::UnusualChange, Severity::Warning, Some(3),
+                                       format!("Change {} is neither 0 (receive) nor 1 (change)", change)));
+        }
+    }
+    if let Some(index) = values.get(4).map(|v| v.as_number()) {
+        if index > config.max_index {
+            warnings.push(Warning::new(WarningCode::LargeIndex, Severity::Warning, Some(4),
+                                       format!("Address index {} is beyond the gap limit scan {}", index, config.max_index)));
+        }
+    }
+    warnings
+}
+
+/// Validate the path as a standard path, or as a prefix of it (i.e., an account or a chain path) with the
+/// missing elements set to zero
+fn parse_standard(values: &[PathValue]) -> Result<(), Error> {
+    const TAIL: [PathValue; 5] = [
+        PathValue::Hardened(0), PathValue::Hardened(0), PathValue::Hardened(0),
+        PathValue::Normal(0), PathValue::Normal(0),
+    ];
+    let len = values.len();
+    if len > TAIL.len() {
+        return Err(Error::InvalidLength(len))
+    }
+    let mut full = values.to_vec();
+    if len <= 3 {
+        full.extend_from_slice(&TAIL[len..3]);
+        AccountHDPath::try_from(CustomHDPath(full)).map(|_| ())
+    } else {
+        full.extend_from_slice(&TAIL[len..]);
+        StandardHDPath::try_from(CustomHDPath(full)).map(|_| ())
+    }
+}
+
+/// Find the elements of the standard structure with wrong hardening
+fn check_hardened(values: &[PathValue]) -> Vec<Warning> {
+    let mut warnings = vec![];
+    let hardened = [
+        (WarningCode::NormalPurpose, "Purpose"),
+        (WarningCode::NormalCoinType, "Coin type"),
+        (WarningCode::NormalAccount, "Account"),
+    ];
+    for (position, (code, name)) in hardened.iter().enumerate() {
+        if let Some(PathValue::Normal(n)) = values.get(position) {
+            warnings.push(Warning::new(*code, Severity::Error, Some(position),
+                                       format!("{} must be hardened, expected {}' instead of {}", name, n, n)));
+        }
+    }
+    let normal = [
+        (WarningCode::HardenedChange, "Change"),
+        (WarningCode::HardenedIndex, "Address index"),
+    ];
+    for (i, (code, name)) in normal.iter().enumerate() {
+        if let Some(PathValue::Hardened(n)) = values.get(i + 3) {
+            warnings.push(Warning::new(*code, Severity::Error, Some(i + 3),
+                                       format!("{} must not be hardened, expected {} instead of {}'", name, n, n)));
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountHDPath, StandardHDPath};
+    use std::str::FromStr;
+
+    fn codes(path: &str, config: &LintConfig) -> Vec<&'static str> {
+        let hd_path = CustomHDPath::from_str(path).unwrap();
+        lint(&hd_path, config).iter().map(|w| w.code.code()).collect()
+    }
+
+    #[test]
+    pub fn no_warnings_for_standard() {
+        let config = LintConfig::default();
+        assert!(codes("m/44'/0'/0'/0/0", &config).is_empty());
+        assert!(codes("m/84'/0'/3'/1/100", &config).is_empty());
+        assert!(codes("m/86'/1'/0'/0/0", &config).is_empty());
+        assert!(codes("m/44'/60'/0'/0/0", &config).is_empty());
+        assert!(codes("m/84'/0'/0'", &config).is_empty());
+        let hd_path = StandardHDPath::from_str("m/49'/2'/0'/0/5").unwrap();
+        assert!(lint(&hd_path, &config).is_empty());
+        let hd_path = AccountHDPath::from_str("m/84'/0'/1'").unwrap();
+        assert!(lint(&hd_path, &config).is_empty());
+    }
+
+    #[test]
+    pub fn ignores_other_structures() {
+        let config = LintConfig::default();
+        assert!(codes("m/0/100000", &config).is_empty());
+        assert!(codes("m/48'/0'/0'/2'/0/0", &config).is_empty());
+        assert!(codes("m/1/2/3/4/5", &config).is_empty());
+    }
+
+    #[test]
+    pub fn hardened_structure() {
+        let config = LintConfig::default();
+        assert_eq!(vec!["HD004"], codes("m/84'/0'/0'/0'/0", &config));
+        assert_eq!(vec!["HD005"], codes("m/84'/0'/0'/0/0'", &config));
+        assert_eq!(vec!["HD003"], codes("m/84'/0'/0/0/0", &config));
+        assert_eq!(vec!["HD001", "HD002", "HD003"], codes("m/44/0/0/0/0", &config));
+        assert_eq!(vec!["HD004"], codes("m/84'/0'/0'/1'", &config));
+        assert_eq!(vec!["HD002"], codes("m/84'/0", &config));
+        assert!(codes("m/84'/0'/0'/1", &config).is_empty());
+        assert!(codes("m/84'", &config).is_empty());
+    }
+
+    #[test]
+    pub fn too_long() {
+        let config = LintConfig::default();
+        assert_eq!(vec!["HD012"], codes("m/84'/0'/0'/0/0/0", &config));
+        assert_eq!(vec!["HD012"], codes("m/44'/0'/0'/0/0/1'/2", &config));
+        assert_eq!(vec!["HD004", "HD012"], codes("m/84'/0'/0'/0'/0/0", &config));
+        let hd_path = CustomHDPath::from_str("m/84'/0'/0'/0/0/0").unwrap();
+        assert_eq!(Some(5), lint(&hd_path, &config)[0].position);
+    }
+
+    #[test]
+    pub fn segwit_coins() {
+        let config = LintConfig::default();
+        assert_eq!(vec!["HD007"], codes("m/84'/145'/0'/0/0", &config));
+        assert_eq!(vec!["HD007"], codes("m/49'/60'/0'/0/0", &config));
+        assert!(codes("m/44'/145'/0'/0/0", &config).is_empty());
+        assert!(codes("m/84'/2'/0'/0/0", &config).is_empty());
+    }
+
+    #[test]
+    pub fn unusual_values() {
+        let config = LintConfig::default();
+        assert_eq!(vec!["HD006"], codes("m/44'/0'/0'/2/0", &config));
+        assert_eq!(vec!["HD008"], codes("m/44'/0'/0'/0/10001", &config));
+        assert!(codes("m/44'/0'/0'/0/10000", &config).is_empty());
+        assert_eq!(vec!["HD009"], codes("m/44'/0'/5000'/0/0", &config));
+        let config = LintConfig { max_index: 20, ..LintConfig::default() };
+        assert_eq!(vec!["HD008"], codes("m/44'/0'/0'/0/21", &config));
+    }
+
+    #[test]
+    pub fn network() {
+        let testnet = LintConfig { network: TargetNetwork::Testnet, ..LintConfig::default() };
+        assert_eq!(vec!["HD010"], codes("m/84'/0'/0'/0/0", &testnet));
+        assert!(codes("m/84'/1'/0'/0/0", &testnet).is_empty());
+        let mainnet = LintConfig { network: TargetNetwork::Mainnet, ..LintConfig::default() };
+        assert_eq!(vec!["HD011"], codes("m/84'/1'/0'/0/0", &mainnet));
+        assert!(codes("m/84'/0'/0'/0/0", &mainnet).is_empty());
+        assert!(codes("m/84'/1'/0'/0/0", &LintConfig::default()).is_empty());
+    }
+
+    #[test]
+    pub fn ignore_codes() {
+        let config = LintConfig { ignore: vec![WarningCode::UnusualChange], ..LintConfig::default() };
+        assert!(codes("m/44'/0'/0'/2/0", &config).is_empty());
+    }
+
+    #[test]
+    pub fn severity_and_display() {
+        let hd_path = CustomHDPath::from_str("m/84'/0'/0'/0'/0").unwrap();
+        let warnings = lint(&hd_path, &LintConfig::default());
+        assert_eq!(Some(3), warnings[0].position);
+        assert_eq!("HD004 error: Change must not be hardened, expected 0 instead of 0'", warnings[0].to_string());
+        assert!(Severity::Error > Severity::Warning);
+        assert!(Severity::Warning > Severity::Info);
+    }
+
+    struct NoEthereum;
+
+    impl LintRule for NoEthereum {
+        fn check(&self, hd_path: &dyn HDPath, _config: &LintConfig) -> Vec<Warning> {
+            match hd_path.get(1) {
+                Some(PathValue::Hardened(60)) => vec![
+                    Warning::new(WarningCode::Custom("X001"), Severity::Info, Some(1), "Ethereum path".to_string())
+                ],
+                _ => vec![]
+            }
+        }
+    }
+
+    #[test]
+    pub fn custom_rule() {
+        let hd_path = CustomHDPath::from_str("m/44'/60'/0'/0/0").unwrap();
+        let warnings = lint_with(&hd_path, &LintConfig::default(), &[&NoEthereum]);
+        assert_eq!(1, warnings.len());
+        assert_eq!("X001", warnings[0].code.code());
+        let config = LintConfig { ignore: vec![WarningCode::Custom("X001")], ..LintConfig::default() };
+        assert!(lint_with(&hd_path, &config, &[&NoEthereum]).is_empty());
+    }
+}
@@ -0,0 +1,224 @@
+//! Compile-time parsing of HD Path literals, used by the [`hdpath!`](../macro.hdpath.html) macro.
+//! All functions here panic on an invalid input, which becomes a compile error when called in a const context.
+
+use crate::{AccountHDPath, ParseOptions, PathValue, Purpose, ShortHDPath, StandardHDPath};
+use crate::parser::{self, ParseError};
+
+const ERR_FORMAT: &str = "Invalid HD Path format";
+const ERR_VALUE: &str = "Invalid HD Path: value is too large";
+const ERR_STANDARD: &str = "Invalid HD Path: expected m/purpose'/coin_type'/account'/change/index";
+const ERR_ACCOUNT: &str = "Invalid HD Path: expected m/purpose'/coin_type'/account'";
+const ERR_SHORT: &str = "Invalid HD Path: expected m/purpose'/coin_type'/account'/index";
+
+const fn parse_into(value: &[u8], out: &mut [PathValue]) -> Result<usize, &'static str> {
+    match parser::parse_into(value, &ParseOptions::DEFAULT, out) {
+        Ok(n) => Ok(n),
+        Err(ParseError::Value) => Err(ERR_VALUE),
+        Err(_) => Err(ERR_FORMAT),
+    }
+}
+
+/// Number of elements in the path
+pub const fn count(value: &str) -> usize {
+    match parse_into(value.as_bytes(), &mut []) {
+        Ok(n) => n,
+        Err(msg) => panic!("{}", msg),
+    }
+}
+
+/// Elements of the path, where `N` must be the number of elements returned by `count`
+pub const fn values<const N: usize>(value: &str) -> [PathValue; N] {
+    let mut result = [PathValue::Normal(0); N];
+    match parse_into(value.as_bytes(), &mut result) {
+        Ok(n) if n == N => result,
+        Ok(_) => panic!("{}", ERR_FORMAT),
+        Err(msg) => panic!("{}", msg),
+    }
+}
+
+const fn purpose(value: PathValue, msg: &'static str) -> Purpose {
+    match value {
+        PathValue::Hardened(n) => match Purpose::try_from_number(n) {
+            Ok(p) => p,
+            Err(_) => panic!("{}", ERR_VALUE),
+        },
+        PathValue::Normal(_) => panic!("{}", msg),
+    }
+}
+
+const fn hardened(value: PathValue, msg: &'static str) -> u32 {
+    match value {
+        PathValue::Hardened(n) => n,
+        PathValue::Normal(_) => panic!("{}", msg),
+    }
+}
+
+const fn normal(value: PathValue, msg: &'static str) -> u32 {
+    match value {
+        PathValue::Normal(n) => n,
+        PathValue::Hardened(_) => panic!("{}", msg),
+    }
+}
+
+const fn exact<const N: usize>(value: &[u8], msg: &'static str) -> [PathValue; N] {
+    let mut result = [PathValue::Normal(0); N];
+    match parse_into(value, &mut result) {
+        Ok(n) if n == N => result,
+        Ok(_) => panic!("{}", msg),
+        Err(e) => panic!("{}", e),
+    }
+}
+
+pub const fn standard(value: &str) -> StandardHDPath {
+    let v = exact::<5>(value.as_bytes(), ERR_STANDARD);
+    match StandardHDPath::try_new_const(
+        purpose(v[0], ERR_STANDARD),
+        hardened(v[1], ERR_STANDARD),
+        hardened(v[2], ERR_STANDARD),
+        normal(v[3], ERR_STANDARD),
+        normal(v[4], ERR_STANDARD),
+    ) {
+        Ok(p) => p,
+        Err(_) => panic!("{}", ERR_VALUE),
+    }
+}
+
+/// Account path, which may have `/x/x` suffix
+pub const fn account(value: &str) -> AccountHDPath {
+    let mut bytes = value.as_bytes();
+    if bytes.len() > 4 {
+        let (head, tail) = bytes.split_at(bytes.len() - 4);
+        if tail[0] == b'/' && tail[1] == b'x' && tail[2] == b'/' && tail[3] == b'x' {
+            bytes = head;
+        }
+    }
+    let v = exact::<3>(bytes, ERR_ACCOUNT);
+    match AccountHDPath::try_new_const(
+        purpose(v[0], ERR_ACCOUNT),
+        hardened(v[1], ERR_ACCOUNT),
+        hardened(v[2], ERR_ACCOUNT),
+    ) {
+        Ok(p) => p,
+        Err(_) => panic!("{}", ERR_VALUE),
+    }
+}
+
+pub const fn short(value: &str) -> ShortHDPath {
+    let v = exact::<4>(value.as_bytes(), ERR_SHORT);
+    ShortHDPath {
+        purpose: purpose(v[0], ERR_SHORT),
+        coin_type: hardened(v[1], ERR_SHORT),
+        account: hardened(v[2], ERR_SHORT),
+        index: normal(v[3], ERR_SHORT),
+    }
+}
+
+/// Create an HD Path from a string literal, which is parsed and validated at compile time.
+/// An invalid path, or a path with a structure not matching the requested type, gives a compile error.
+///
+/// - `hdpath!("m/...")` gives a [`CustomHDPath`](struct.CustomHDPath.html)
+/// - `hdpath!(standard "m/...")` gives a [`StandardHDPath`](struct.StandardHDPath.html)
+/// - `hdpath!(account "m/...")` gives an [`AccountHDPath`](struct.AccountHDPath.html)
+/// - `hdpath!(short "m/...")` gives a [`ShortHDPath`](struct.ShortHDPath.html)
+///
+/// Standard, Account and Short paths are constructed as constants, so the macro can be used to define a `const`.
+///
+/// # Usage
+/// ```
+/// use hdpath::{hdpath, StandardHDPath, AccountHDPath, CustomHDPath, Purpose};
+///
+/// const DEFAULT_PATH: StandardHDPath = hdpath!(standard "m/84'/0'/0'/0/0");
+/// assert_eq!(StandardHDPath::new(Purpose::Witness, 0, 0, 0, 0), DEFAULT_PATH);
+///
+/// let account: AccountHDPath = hdpath!(account "m/44'/60'/160720'/x/x");
+/// let custom: CustomHDPath = hdpath!("m/48'/0'/0'/2'/0/1");
+/// ```
+///
+/// Invalid literal doesn't compile:
+/// ```compile_fail
+/// use hdpath::hdpath;
+/// let hd_path = hdpath!(standard "m/84'/0'/0'/0'/0");
+/// ```
+/// ```compile_fail
+/// use hdpath::hdpath;
+/// let hd_path = hdpath!("m/44'/99999999999'");
+/// ```
+#[macro_export]
+macro_rules! hdpath {
+    (standard $value:literal) => {{
+        const HD_PATH: $crate::StandardHDPath = $crate::literal::standard($value);
+        HD_PATH
+    }};
+    (account $value:literal) => {{
+        const HD_PATH: $crate::AccountHDPath = $crate::literal::account($value);
+        HD_PATH
+    }};
+    (short $value:literal) => {{
+        const HD_PATH: $crate::ShortHDPath = $crate::literal::short($value);
+        HD_PATH
+    }};
+    ($value:literal) => {{
+        const VALUES: [$crate::PathValue; $crate::literal::count($value)] = $crate::literal::values($value);
+        $crate::CustomHDPath(VALUES.to_vec())
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CustomHDPath, PathValue};
+    use std::str::FromStr;
+
+    #[test]
+    pub fn create_standard() {
+        let paths = [
+            (hdpath!(standard "m/44'/0'/0'/0/0"), "m/44'/0'/0'/0/0"),
+            (hdpath!(standard "m/84'/0'/1'/1/101"), "m/84'/0'/1'/1/101"),
+            (hdpath!(standard "M/49H/60H/160720H/0/2147483647"), "m/49'/60'/160720'/0/2147483647"),
+            (hdpath!(standard "m/101'/0'/0'/0/0"), "m/101'/0'/0'/0/0"),
+        ];
+        for (act, exp) in paths {
+            assert_eq!(crate::StandardHDPath::from_str(exp).unwrap(), act);
+        }
+    }
+
+    #[test]
+    pub fn create_account() {
+        assert_eq!(crate::AccountHDPath::from_str("m/84'/0'/3'").unwrap(), hdpath!(account "m/84'/0'/3'"));
+        assert_eq!(crate::AccountHDPath::from_str("m/84'/0'/3'").unwrap(), hdpath!(account "m/84'/0'/3'/x/x"));
+    }
+
+    #[test]
+    pub fn create_short() {
+        assert_eq!(crate::ShortHDPath::from_str("m/44'/60'/0'/5").unwrap(), hdpath!(short "m/44'/60'/0'/5"));
+    }
+
+    #[test]
+    pub fn create_custom() {
+        assert_eq!(CustomHDPath::from_str("m/48'/0'/0'/2'/0/1").unwrap(), hdpath!("m/48'/0'/0'/2'/0/1"));
+        assert_eq!(CustomHDPath(vec![PathValue::Normal(0)]), hdpath!("m/0"));
+        assert_eq!(CustomHDPath(vec![PathValue::Hardened(2147483647)]), hdpath!("m/2147483647'"));
+    }
+
+    #[test]
+    pub fn parse_as_from_str() {
+        let paths = [
+            "m/0", "m/0'", "M/1H/2", "m/44'/0'/0'/0/0", "m/1/2/3/4/5/6/7/8/9/10",
+            "", "m", "m/", "m//0", "m/0/", "m/0''", "m/'", "m/H0", "m/0x", "m/44/x/x",
+            "m/2147483648", "m/-1", "x/0",
+        ];
+        for p in paths {
+            let mut out = [PathValue::Normal(0); 16];
+            let act = super::parse_into(p.as_bytes(), &mut out)
+                .map(|n| CustomHDPath(out[0..n].to_vec()));
+            assert_eq!(CustomHDPath::from_str(p).ok(), act.ok(), "path: {}", p);
+        }
+    }
+
+    #[test]
+    pub fn err_on_overflow() {
+        let paths = ["m/4294967296", "m/99999999999", "m/0/44'/99999999999999999999'"];
+        for p in paths {
+            assert!(super::parse_into(p.as_bytes(), &mut []).is_err(), "path: {}", p);
+        }
+    }
+}
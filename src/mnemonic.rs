@@ -0,0 +1,125 @@
+use crate::{Error, Seed};
+use bip39::{Language, Mnemonic};
+use zeroize::Zeroize;
+
+impl Seed {
+
+    /// Make a seed from a [BIP-39](https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki) mnemonic phrase
+    /// and a passphrase (which is an empty string if not used). The language of the mnemonic is detected automatically.
+    ///
+    /// Returns `Error::InvalidMnemonic` if the mnemonic has unknown words, an invalid length or an invalid checksum.
+    ///
+    /// ```
+    /// use hdpath::{Seed, StandardHDPath};
+    /// use bitcoin::NetworkKind;
+    /// use bitcoin::secp256k1::Secp256k1;
+    /// # use std::str::FromStr;
+    ///
+    /// let secp = Secp256k1::new();
+    /// let seed = Seed::from_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", "").unwrap();
+    /// let hd_path = StandardHDPath::from_str("m/84'/0'/0'/0/0").unwrap();
+    /// let key = seed.derive_priv(&secp, NetworkKind::Main, &hd_path).unwrap();
+    /// ```
+    pub fn from_mnemonic(phrase: &str, passphrase: &str) -> Result<Seed, Error> {
+        let mnemonic = Mnemonic::parse(phrase).map_err(|_| Error::InvalidMnemonic)?;
+        Seed::from_mnemonic_value(mnemonic, passphrase)
+    }
+
+    /// Make a seed from the mnemonic phrase in the specified language and a passphrase (which is an empty string if not used).
+    pub fn from_mnemonic_in(language: Language, phrase: &str, passphrase: &str) -> Result<Seed, Error> {
+        let mnemonic = Mnemonic::parse_in(language, phrase).map_err(|_| Error::InvalidMnemonic)?;
+        Seed::from_mnemonic_value(mnemonic, passphrase)
+    }
+
+    fn from_mnemonic_value(mnemonic: Mnemonic, passphrase: &str) -> Result<Seed, Error> {
+        let mut value = mnemonic.to_seed(passphrase);
+        let seed = Seed::from_bytes(&value);
+        value.zeroize();
+        seed
+    }
+
+    /// Check if the phrase is a valid mnemonic in any of the supported languages, including its checksum
+    pub fn is_valid_mnemonic(phrase: &str) -> bool {
+        Mnemonic::parse(phrase).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StandardHDPath;
+    use bitcoin::NetworkKind;
+    use bitcoin::secp256k1::Secp256k1;
+    use std::str::FromStr;
+
+    fn hex(value: &[u8]) -> String {
+        value.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    pub fn bip39_vectors() {
+        // from https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+        let vectors = [
+            ("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+             "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"),
+            ("legal winner thank year wave sausage worth useful legal winner thank yellow",
+             "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607"),
+            ("letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+             "d71de856f81a8acc65e6fc851a38d4d7ec216fd0796d0a6827a3ad6ed5511a30fa280f12eb2e47ed2ac03b5c462a0358d18d69fe4f985ec81778c1b370b652a8"),
+            ("zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+             "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069"),
+        ];
+        for (phrase, seed) in vectors {
+            let act = Seed::from_mnemonic(phrase, "TREZOR").unwrap();
+            assert_eq!(seed, hex(act.expose_secret()), "phrase: {}", phrase);
+            let act = Seed::from_mnemonic_in(Language::English, phrase, "TREZOR").unwrap();
+            assert_eq!(seed, hex(act.expose_secret()), "phrase: {}", phrase);
+        }
+    }
+
+    #[test]
+    pub fn japanese_vector() {
+        // from https://github.com/bip32JP/bip32JP.github.io/blob/master/test_JP_BIP39.json
+        let phrase = "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あおぞら";
+        let act = Seed::from_mnemonic(phrase, "㍍ガバヴァぱばぐゞちぢ十人十色").unwrap();
+        assert_eq!(
+            "a262d6fb6122ecf45be09c50492b31f92e9beb7d9a845987a02cefda57a15f9c467a17872029a9e92299b5cbdf306e3a0ee620245cbd508959b6cb7ca637bd55",
+            hex(act.expose_secret())
+        );
+    }
+
+    #[test]
+    pub fn derive_from_mnemonic() {
+        let secp = Secp256k1::new();
+        let seed = Seed::from_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", "").unwrap();
+        let key = seed.derive_priv(&secp, NetworkKind::Main, &StandardHDPath::from_str("m/84'/0'/0'/0/0").unwrap()).unwrap();
+        // from BIP-84 test vectors
+        assert_eq!(
+            "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c",
+            key.public_key(&secp).to_string()
+        );
+    }
+
+    #[test]
+    pub fn invalid_mnemonic() {
+        let phrases = [
+            "",
+            // invalid checksum
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon",
+            // unknown word
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon foobar",
+            // invalid length
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        ];
+        for phrase in phrases {
+            assert!(!Seed::is_valid_mnemonic(phrase), "phrase: {}", phrase);
+            assert_eq!(Err(Error::InvalidMnemonic), Seed::from_mnemonic(phrase, "").map(|_| ()));
+        }
+    }
+
+    #[test]
+    pub fn wrong_language() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert!(Seed::from_mnemonic_in(Language::French, phrase, "").is_err());
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{CustomHDPath, Error, PathValue};
+
+/// Options for parsing an HD Path from a string.
+///
+/// There are three predefined modes:
+/// - `ParseOptions::default()` - the format accepted by `FromStr`, i.e. `m/44'/0'/0'/0/0` or `M/44H/0H/0H/0/0`
+/// - `ParseOptions::strict()` - only the canonical format, i.e. `m/44'/0'/0'/0/0` without leading zeros
+/// - `ParseOptions::lenient()` - anything that looks like an HD Path, including `44h/0h/0h/0/0` or ` m/44'/0'/0' `
+///
+/// # Usage
+/// ```
+/// use hdpath::{CustomHDPath, ParseOptions, StandardHDPath};
+/// # use std::convert::TryFrom;
+///
+/// let hd_path = CustomHDPath::parse_with("44h/60h/0h/0/0", &ParseOptions::lenient()).unwrap();
+/// let hd_path = StandardHDPath::try_from(hd_path).unwrap();
+///
+/// assert!(CustomHDPath::parse_with("m/44'/60'/0'/0/01", &ParseOptions::strict()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ParseOptions {
+    /// Path must start with `m/`
+    pub require_prefix: bool,
+    /// Accept `M/` as a prefix
+    pub allow_uppercase_prefix: bool,
+    /// Accept `'` as a hardened marker
+    pub allow_apostrophe: bool,
+    /// Accept `h` as a hardened marker
+    pub allow_lowercase_h: bool,
+    /// Accept `H` as a hardened marker
+    pub allow_uppercase_h: bool,
+    /// Accept numbers like `01`
+    pub allow_leading_zeros: bool,
+    /// Ignore whitespace at the beginning and at the end
+    pub trim_whitespace: bool,
+}
+
+impl ParseOptions {
+    pub(crate) const DEFAULT: ParseOptions = ParseOptions {
+        require_prefix: true,
+        allow_uppercase_prefix: true,
+        allow_apostrophe: true,
+        allow_lowercase_h: false,
+        allow_uppercase_h: true,
+        allow_leading_zeros: true,
+        trim_whitespace: false,
+    };
+
+    /// Canonical format only, i.e. `m/` prefix, `'` as a hardened marker, and no leading zeros
+    pub const fn strict() -> ParseOptions {
+        ParseOptions {
+            require_prefix: true,
+            allow_uppercase_prefix: false,
+            allow_apostrophe: true,
+            allow_lowercase_h: false,
+            allow_uppercase_h: false,
+            allow_leading_zeros: false,
+            trim_whitespace: false,
+        }
+    }
+
+    /// Accept any of `'`, `h` or `H` markers, optional `m/` prefix, and whitespace around the path
+    pub const fn lenient() -> ParseOptions {
+        ParseOptions {
+            require_prefix: false,
+            allow_uppercase_prefix: true,
+            allow_apostrophe: true,
+            allow_lowercase_h: true,
+            allow_uppercase_h: true,
+            allow_leading_zeros: true,
+            trim_whitespace: true,
+        }
+    }
+
+    const fn is_marker(&self, c: u8) -> bool {
+        (c == b'\'' && self.allow_apostrophe)
+            || (c == b'h' && self.allow_lowercase_h)
+            || (c == b'H' && self.allow_uppercase_h)
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions::DEFAULT
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum ParseError {
+    /// Invalid characters or structure
+    Format,
+    /// Number is too large for an HD Path element
+    Value,
+    /// More than 255 elements, with the actual number of elements
+    Length(usize),
+}
+
+impl From<ParseError> for Error {
+    fn from(value: ParseError) -> Self {
+        match value {
+            ParseError::Length(len) => Error::InvalidLength(len),
+            ParseError::Format | ParseError::Value => Error::InvalidFormat,
+        }
+    }
+}
+
+/// Parse the path and write its elements into `out` (as much as it has space for).
+/// Returns number of the elements in the path.
+///
+/// It's a `const fn`, so the same parser is used for both runtime parsing and for compile-time literals.
+pub(crate) const fn parse_into(value: &[u8], options: &ParseOptions, out: &mut [PathValue]) -> Result<usize, ParseError> {
+    let mut value = value;
+    if options.trim_whitespace {
+        while !value.is_empty() && value[0].is_ascii_whitespace() {
+            value = value.split_at(1).1;
+        }
+        while !value.is_empty() && value[value.len() - 1].is_ascii_whitespace() {
+            value = value.split_at(value.len() - 1).0;
+        }
+    }
+    let mut pos = 0;
+    if value.len() >= 2 && value[1] == b'/' && (value[0] == b'm' || (value[0] == b'M' && options.allow_uppercase_prefix)) {
+        pos = 2;
+    } else if options.require_prefix {
+        return Err(ParseError::Format);
+    }
+    if pos == value.len() {
+        return Err(ParseError::Format);
+    }
+    let mut count = 0;
+    while pos < value.len() {
+        let start = pos;
+        let mut num: u32 = 0;
+        while pos < value.len() && value[pos].is_ascii_digit() {
+            num = match num.checked_mul(10) {
+                Some(n) => match n.checked_add((value[pos] - b'0') as u32) {
+                    Some(n) => n,
+                    None => return Err(ParseError::Value),
+                },
+                None => return Err(ParseError::Value),
+            };
+            pos += 1;
+        }
+        if pos == start {
+            return Err(ParseError::Format);
+        }
+        if !options.allow_leading_zeros && value[start] == b'0' && pos - start > 1 {
+            return Err(ParseError::Format);
+        }
+        if !PathValue::is_ok(num) {
+            return Err(ParseError::Value);
+        }
+        let hardened = pos < value.len() && options.is_marker(value[pos]);
+        if hardened {
+            pos += 1;
+        }
+        if pos < value.len() {
+            if value[pos] != b'/' || pos + 1 == value.len() {
+                return Err(ParseError::Format);
+            }
+            pos += 1;
+        }
+        if count < out.len() {
+            out[count] = if hardened { PathValue::Hardened(num) } else { PathValue::Normal(num) };
+        }
+        count += 1;
+    }
+    if count > 0xff {
+        return Err(ParseError::Length(count));
+    }
+    Ok(count)
+}
+
+/// Parse a path into a new `CustomHDPath`
+pub(crate) fn parse(value: &str, options: &ParseOptions) -> Result<CustomHDPath, Error> {
+    let len = parse_into(value.as_bytes(), options, &mut [])?;
+    let mut keys = vec![PathValue::Normal(0); len];
+    parse_into(value.as_bytes(), options, &mut keys)?;
+    Ok(CustomHDPath(keys))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HDPath, StandardHDPath};
+    #[cfg(not(target_arch = "wasm32"))]
+    use quickcheck::quickcheck;
+    use std::str::FromStr;
+
+    fn parse_str(value: &str, options: &ParseOptions) -> Option<String> {
+        parse(value, options).ok().map(|p| p.to_string())
+    }
+
+    #[test]
+    pub fn default_mode() {
+        let options = ParseOptions::default();
+        assert_eq!(Some("m/44'/0'/0'/0/0".to_string()), parse_str("m/44'/0'/0'/0/0", &options));
+        assert_eq!(Some("m/44'/0'/0'/0/0".to_string()), parse_str("M/44H/0H/0H/0/0", &options));
+        assert_eq!(Some("m/44'/0'/1'".to_string()), parse_str("m/044'/00'/01'", &options));
+        assert_eq!(None, parse_str("m/44h/0h/0h/0/0", &options));
+        assert_eq!(None, parse_str("44'/0'/0'/0/0", &options));
+        assert_eq!(None, parse_str(" m/44'/0'/0'/0/0", &options));
+        assert_eq!(None, parse_str("m/44'/0'/0'/0/0 ", &options));
+    }
+
+    #[test]
+    pub fn strict_mode() {
+        let options = ParseOptions::strict();
+        assert_eq!(Some("m/44'/0'/0'/0/0".to_string()), parse_str("m/44'/0'/0'/0/0", &options));
+        assert_eq!(Some("m/0/10/100".to_string()), parse_str("m/0/10/100", &options));
+        let invalid = [
+            "M/44'/0'/0'/0/0", "m/44H/0'/0'/0/0", "m/44h/0'/0'/0/0",
+            "m/044'/0'/0'/0/0", "m/44'/00'/0'/0/0", "m/44'/0'/0'/0/01",
+            "44'/0'/0'/0/0", " m/44'/0'/0'/0/0",
+        ];
+        for p in invalid {
+            assert_eq!(None, parse_str(p, &options), "path: {}", p);
+        }
+    }
+
+    #[test]
+    pub fn lenient_mode() {
+        let options = ParseOptions::lenient();
+        let valid = [
+            "m/44'/60'/0'/0/0", "M/44'/60'/0'/0/0", "m/44h/60h/0h/0/0", "m/44H/60H/0H/0/0",
+            "44'/60'/0'/0/0", "44h/60'/0H/0/0", "  m/44'/60'/0'/0/0\n", "\t44h/60h/0h/0/0 ",
+            "m/044'/060'/00'/0/0",
+        ];
+        for p in valid {
+            assert_eq!(Some("m/44'/60'/0'/0/0".to_string()), parse_str(p, &options), "path: {}", p);
+        }
+        let invalid = [
+            "", " ", "m", "m/", "/44'", "m/44 '/0", "m/44'/ 0", "m/44''", "m/44'/", "44'/60'/",
+            "m/44x", "m//0", "h", "m/h",
+        ];
+        for p in invalid {
+            assert_eq!(None, parse_str(p, &options), "path: {}", p);
+        }
+    }
+
+    #[test]
+    pub fn no_overflow() {
+        let paths = [
+            "m/4294967295", "m/4294967296", "m/99999999999", "m/0/99999999999999999999999999999'",
+            "m/2147483648", "m/2147483648'",
+        ];
+        for p in paths {
+            assert_eq!(None, parse_str(p, &ParseOptions::default()), "path: {}", p);
+            assert_eq!(None, parse_str(p, &ParseOptions::lenient()), "path: {}", p);
+        }
+        assert_eq!(Some("m/2147483647'".to_string()), parse_str("m/2147483647'", &ParseOptions::default()));
+    }
+
+    #[test]
+    pub fn max_length() {
+        let long = format!("m{}", "/1".repeat(255));
+        assert_eq!(255, parse(long.as_str(), &ParseOptions::default()).unwrap().len());
+        let too_long = format!("m{}", "/1".repeat(256));
+        assert_eq!(Err(Error::InvalidLength(256)), parse(too_long.as_str(), &ParseOptions::default()));
+        let too_long = format!("m{}", "/1'".repeat(300));
+        assert_eq!(Err(Error::InvalidLength(300)), CustomHDPath::from_str(too_long.as_str()));
+        // syntax errors are still reported as format errors
+        assert_eq!(Err(Error::InvalidFormat), parse("m/1/x", &ParseOptions::default()));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    quickcheck! {
+        fn never_panics(value: String) -> bool {
+            let _ = parse(value.as_str(), &ParseOptions::default());
+            let _ = parse(value.as_str(), &ParseOptions::strict());
+            let _ = parse(value.as_str(), &ParseOptions::lenient());
+            true
+        }
+
+        fn parse_what_is_displayed(purpose: u32, coin: u32, account: u32, change: u32, index: u32) -> bool {
+            let hd_path = StandardHDPath::try_new(
+                crate::Purpose::Custom(purpose & 0x7fffffff), coin & 0x7fffffff, account & 0x7fffffff, change & 0x7fffffff, index & 0x7fffffff
+            ).unwrap();
+            let value = hd_path.to_string();
+            parse(value.as_str(), &ParseOptions::strict()).map(|p| p.to_string()) == Ok(value.clone())
+                && StandardHDPath::from_str(value.as_str()) == Ok(hd_path)
+        }
+    }
+}
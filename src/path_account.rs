@@ -84,6 +84,21 @@ impl AccountHDPath {
         })
     }
 
+    /// Try to create an account HD Path in a const context, i.e. same as `try_new` but returns
+    /// `Error::HighBitIsSet` if a field has an incorrect value.
+    pub const fn try_new_const(purpose: Purpose, coin_type: u32, account: u32) -> Result<AccountHDPath, Error> {
+        if !PathValue::is_ok(purpose.as_value().as_number())
+            || !PathValue::is_ok(coin_type)
+            || !PathValue::is_ok(account) {
+            return Err(Error::HighBitIsSet);
+        }
+        Ok(AccountHDPath {
+            purpose,
+            coin_type,
+            account,
+        })
+    }
+
     /// Derive path to an address withing this account path
     /// ```
     /// # use hdpath::{AccountHDPath, Purpose, StandardHDPath};
@@ -160,7 +175,7 @@ impl TryFrom<CustomHDPath> for AccountHDPath {
         if value.0.len() < 3 {
             return Err(Error::InvalidLength(value.0.len()))
         }
-        if let Some(PathValue::Hardened(p)) = value.0.get(0) {
+        if let Some(PathValue::Hardened(p)) = value.0.first() {
             let purpose = Purpose::try_from(*p)?;
             if let Some(PathValue::Hardened(coin_type)) = value.0.get(1) {
                 if let Some(PathValue::Hardened(account)) = value.0.get(2) {
@@ -222,7 +237,7 @@ impl std::convert::From<&AccountHDPath> for Vec<ChildNumber> {
             ChildNumber::from_hardened_idx(value.account)
                 .expect("Account is not Hardened"),
         ];
-        return result.to_vec();
+        result.to_vec()
     }
 }
 
@@ -247,6 +262,19 @@ impl std::convert::From<&AccountHDPath> for DerivationPath {
     }
 }
 
+/// Unlike conversion from `CustomHDPath` it accepts only a path with exactly 3 elements
+#[cfg(feature = "with-bitcoin")]
+impl TryFrom<&DerivationPath> for AccountHDPath {
+    type Error = Error;
+
+    fn try_from(value: &DerivationPath) -> Result<Self, Self::Error> {
+        if value.len() != 3 {
+            return Err(Error::InvalidLength(value.len()))
+        }
+        AccountHDPath::try_from(CustomHDPath::try_from(value)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -380,4 +408,22 @@ mod tests_with_bitcoin {
         assert_eq!(children[2], ChildNumber::from_hardened_idx(2).unwrap());
     }
 
-}
\ No newline at end of file
+    #[test]
+    pub fn convert_from_bitcoin() {
+        let source = DerivationPath::from_str("m/44'/60'/2'").unwrap();
+        let act = AccountHDPath::try_from(&source).unwrap();
+        assert_eq!(AccountHDPath::new(Purpose::Pubkey, 60, 2), act);
+    }
+
+    #[test]
+    pub fn err_convert_from_invalid_bitcoin() {
+        let paths = vec![
+            "m/44'/60'", "m/44'/60'/2'/0", "m/44'/60'/2'/0/0",
+            "m/44/60'/2'", "m/44'/60/2'", "m/44'/60'/2",
+        ];
+        for p in paths {
+            let source = DerivationPath::from_str(p).unwrap();
+            assert!(AccountHDPath::try_from(&source).is_err(), "path: {}", p);
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use crate::{AccountHDPath, CustomHDPath, Error, PathValue, Purpose, StandardHDPath};
+use crate::traits::HDPath;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+#[cfg(feature = "with-bitcoin")]
+use bitcoin::bip32::DerivationPath;
+
+/// SLIP-44 coin type of Cardano
+pub const CARDANO_COIN_TYPE: u32 = 1815;
+
+/// Role of a key in a Cardano wallet, which takes the place of _change_ in the path.
+/// See [CIP-1852](https://cips.cardano.org/cip/CIP-1852) and [CIP-105](https://cips.cardano.org/cip/CIP-0105)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Role {
+    /// `0`, receiving addresses
+    External,
+    /// `1`, change addresses
+    Internal,
+    /// `2`, staking (reward) key
+    Staking,
+    /// `3`, Delegated Representative key
+    DRep,
+    /// `4`, Constitutional Committee cold key
+    CommitteeCold,
+    /// `5`, Constitutional Committee hot key
+    CommitteeHot,
+}
+
+impl Role {
+    pub const fn as_number(&self) -> u32 {
+        match self {
+            Role::External => 0,
+            Role::Internal => 1,
+            Role::Staking => 2,
+            Role::DRep => 3,
+            Role::CommitteeCold => 4,
+            Role::CommitteeHot => 5,
+        }
+    }
+}
+
+impl TryFrom<u32> for Role {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Role::External),
+            1 => Ok(Role::Internal),
+            2 => Ok(Role::Staking),
+            3 => Ok(Role::DRep),
+            4 => Ok(Role::CommitteeCold),
+            5 => Ok(Role::CommitteeHot),
+            _ => Err(Error::InvalidStructure)
+        }
+    }
+}
+
+/// HD Path for Cardano, `m/1852'/1815'/account'/role/index` as defined by [CIP-1852](https://cips.cardano.org/cip/CIP-1852)
+/// for Shelley wallets, or `m/44'/1815'/account'/role/index` for Byron wallets.
+/// Byron paths can have only `External` and `Internal` roles.
+///
+/// # Usage
+/// ```
+/// use hdpath::{CardanoHDPath, Role};
+/// # use std::str::FromStr;
+///
+/// let hd_path = CardanoHDPath::from_str("m/1852'/1815'/0'/2/0").unwrap();
+/// assert_eq!(Role::Staking, hd_path.role());
+///
+/// let hd_path = CardanoHDPath::new(0, Role::External, 5);
+/// assert_eq!("m/1852'/1815'/0'/0/5", hd_path.to_string());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CardanoHDPath {
+    purpose: Purpose,
+    account: u32,
+    role: Role,
+    index: u32,
+}
+
+impl CardanoHDPath {
+
+    /// Create a Shelley path. Panics if account or index is incorrect
+    pub fn new(account: u32, role: Role, index: u32) -> CardanoHDPath {
+        match Self::try_new(Purpose::Cardano, account, role, index) {
+            Ok(path) => path,
+            Err(_) => panic!("Invalid Cardano path: account {}, index {}", account, index)
+        }
+    }
+
+    /// Create a path, where `purpose` is `Purpose::Cardano` (Shelley) or `Purpose::Pubkey` (Byron)
+    pub fn try_new(purpose: Purpose, account: u32, role: Role, index: u32) -> Result<CardanoHDPath, Error> {
+        match purpose.as_value().as_number() {
+            1852 => {}
+            44 => {
+                if role != Role::External && role != Role::Internal {
+                    return Err(Error::InvalidStructure)
+                }
+            }
+            other => return Err(Error::InvalidPurpose(other))
+        }
+        if !PathValue::is_ok(account) || !PathValue::is_ok(index) {
+            return Err(Error::HighBitIsSet)
+        }
+        Ok(CardanoHDPath { purpose, account, role, index })
+    }
+
+    pub fn purpose(&self) -> &Purpose {
+        &self.purpose
+    }
+
+    pub fn account(&self) -> u32 {
+        self.account
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// `true` for a Byron path `m/44'/1815'/...`
+    pub fn is_byron(&self) -> bool {
+        self.purpose == Purpose::Pubkey
+    }
+}
+
+impl HDPath for CardanoHDPath {
+    fn len(&self) -> u8 {
+        5
+    }
+
+    fn get(&self, pos: u8) -> Option<PathValue> {
+        match pos {
+            0 => Some(self.purpose.as_value()),
+            1 => Some(PathValue::Hardened(CARDANO_COIN_TYPE)),
+            2 => Some(PathValue::Hardened(self.account)),
+            3 => Some(PathValue::Normal(self.role.as_number())),
+            4 => Some(PathValue::Normal(self.index)),
+            _ => None
+        }
+    }
+}
+
+impl TryFrom<CustomHDPath> for CardanoHDPath {
+    type Error = Error;
+
+    fn try_from(value: CustomHDPath) -> Result<Self, Self::Error> {
+        let standard = StandardHDPath::try_from(value)?;
+        CardanoHDPath::try_from(&standard)
+    }
+}
+
+impl TryFrom<&StandardHDPath> for CardanoHDPath {
+    type Error = Error;
+
+    fn try_from(value: &StandardHDPath) -> Result<Self, Self::Error> {
+        if value.coin_type() != CARDANO_COIN_TYPE {
+            return Err(Error::InvalidStructure)
+        }
+        CardanoHDPath::try_new(value.purpose().clone(), value.account(), Role::try_from(value.change())?, value.index())
+    }
+}
+
+impl From<&CardanoHDPath> for StandardHDPath {
+    fn from(value: &CardanoHDPath) -> Self {
+        StandardHDPath::new(value.purpose.clone(), CARDANO_COIN_TYPE, value.account, value.role.as_number(), value.index)
+    }
+}
+
+impl From<CardanoHDPath> for StandardHDPath {
+    fn from(value: CardanoHDPath) -> Self {
+        StandardHDPath::from(&value)
+    }
+}
+
+impl From<&CardanoHDPath> for AccountHDPath {
+    fn from(value: &CardanoHDPath) -> Self {
+        AccountHDPath::new(value.purpose.clone(), CARDANO_COIN_TYPE, value.account)
+    }
+}
+
+impl TryFrom<&str> for CardanoHDPath {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        CardanoHDPath::from_str(value)
+    }
+}
+
+impl FromStr for CardanoHDPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = CustomHDPath::from_str(s)?;
+        CardanoHDPath::try_from(value)
+    }
+}
+
+impl fmt::Display for CardanoHDPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m/{}'/{}'/{}'/{}/{}",
+               self.purpose.as_value().as_number(),
+               CARDANO_COIN_TYPE,
+               self.account,
+               self.role.as_number(),
+               self.index
+        )
+    }
+}
+
+#[cfg(feature = "with-bitcoin")]
+impl From<&CardanoHDPath> for DerivationPath {
+    fn from(value: &CardanoHDPath) -> Self {
+        value.as_bitcoin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_shelley() {
+        let hd_path = CardanoHDPath::from_str("m/1852'/1815'/3'/2/0").unwrap();
+        assert_eq!(Purpose::Cardano, *hd_path.purpose());
+        assert_eq!(3, hd_path.account());
+        assert_eq!(Role::Staking, hd_path.role());
+        assert_eq!(0, hd_path.index());
+        assert!(!hd_path.is_byron());
+        assert_eq!("m/1852'/1815'/3'/2/0", hd_path.to_string());
+    }
+
+    #[test]
+    pub fn parse_byron() {
+        let hd_path = CardanoHDPath::from_str("m/44'/1815'/0'/1/7").unwrap();
+        assert!(hd_path.is_byron());
+        assert_eq!(Role::Internal, hd_path.role());
+        assert_eq!("m/44'/1815'/0'/1/7", hd_path.to_string());
+    }
+
+    #[test]
+    pub fn all_roles() {
+        for (n, role) in [Role::External, Role::Internal, Role::Staking, Role::DRep, Role::CommitteeCold, Role::CommitteeHot].iter().enumerate() {
+            assert_eq!(n as u32, role.as_number());
+            assert_eq!(Ok(*role), Role::try_from(n as u32));
+            let hd_path = CardanoHDPath::new(0, *role, 0);
+            assert_eq!(format!("m/1852'/1815'/0'/{}/0", n), hd_path.to_string());
+        }
+        assert_eq!(Err(Error::InvalidStructure), Role::try_from(6));
+    }
+
+    #[test]
+    pub fn err_parse_invalid() {
+        assert_eq!(Err(Error::InvalidStructure), CardanoHDPath::from_str("m/1852'/1815'/0'/6/0"));
+        assert_eq!(Err(Error::InvalidStructure), CardanoHDPath::from_str("m/1852'/0'/0'/0/0"));
+        assert_eq!(Err(Error::InvalidStructure), CardanoHDPath::from_str("m/44'/1815'/0'/2/0"));
+        assert_eq!(Err(Error::InvalidPurpose(84)), CardanoHDPath::from_str("m/84'/1815'/0'/0/0"));
+        assert_eq!(Err(Error::InvalidStructure), CardanoHDPath::from_str("m/1852'/1815'/0'/0'/0"));
+        assert_eq!(Err(Error::InvalidLength(3)), CardanoHDPath::from_str("m/1852'/1815'/0'"));
+    }
+
+    #[test]
+    pub fn err_create_invalid() {
+        assert_eq!(Err(Error::HighBitIsSet), CardanoHDPath::try_new(Purpose::Cardano, 0x80000000, Role::External, 0));
+        assert_eq!(Err(Error::HighBitIsSet), CardanoHDPath::try_new(Purpose::Cardano, 0, Role::External, 0x80000000));
+        assert_eq!(Err(Error::InvalidStructure), CardanoHDPath::try_new(Purpose::Pubkey, 0, Role::Staking, 0));
+        assert_eq!(Err(Error::InvalidPurpose(84)), CardanoHDPath::try_new(Purpose::Witness, 0, Role::External, 0));
+    }
+
+    #[test]
+    pub fn create_with_custom_purpose() {
+        let act = CardanoHDPath::try_new(Purpose::Custom(1852), 0, Role::Staking, 0).unwrap();
+        assert_eq!(CardanoHDPath::new(0, Role::Staking, 0), act);
+        assert_eq!("m/1852'/1815'/0'/2/0", act.to_string());
+        let act = CardanoHDPath::try_new(Purpose::Custom(44), 0, Role::Internal, 3).unwrap();
+        assert!(act.is_byron());
+        assert_eq!(Err(Error::InvalidStructure), CardanoHDPath::try_new(Purpose::Custom(44), 0, Role::Staking, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn panic_to_create_invalid() {
+        CardanoHDPath::new(0x80000000, Role::External, 0);
+    }
+
+    #[test]
+    pub fn convert() {
+        let hd_path = CardanoHDPath::new(1, Role::Staking, 0);
+        let standard = StandardHDPath::from(&hd_path);
+        assert_eq!("m/1852'/1815'/1'/2/0", standard.to_string());
+        assert_eq!(hd_path, CardanoHDPath::try_from(&standard).unwrap());
+        assert_eq!("m/1852'/1815'/1'/x/x", AccountHDPath::from(&hd_path).to_string());
+        assert_eq!(hd_path.as_custom(), standard.as_custom());
+        assert_eq!(hd_path.to_bytes(), standard.to_bytes());
+    }
+
+    #[test]
+    pub fn order() {
+        let a = CardanoHDPath::from_str("m/44'/1815'/5'/0/0").unwrap();
+        let b = CardanoHDPath::from_str("m/1852'/1815'/0'/0/0").unwrap();
+        let c = CardanoHDPath::from_str("m/1852'/1815'/0'/2/0").unwrap();
+        assert!(a < b);
+        assert!(b < c);
+    }
+}
+
+#[cfg(all(test, feature = "with-bitcoin"))]
+mod tests_with_bitcoin {
+    use super::*;
+
+    #[test]
+    pub fn convert_to_bitcoin() {
+        let hd_path = CardanoHDPath::new(0, Role::DRep, 0);
+        assert_eq!(DerivationPath::from_str("m/1852'/1815'/0'/3/0").unwrap(), DerivationPath::from(&hd_path));
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{PathValue, Error};
-use std::convert::TryFrom;
+use crate::{PathValue, Error, ParseOptions};
+use crate::parser;
+use std::convert::{TryFrom, TryInto};
 #[cfg(feature = "with-bitcoin")]
 use bitcoin::bip32::{ChildNumber, DerivationPath};
 use std::str::FromStr;
@@ -47,6 +48,30 @@ impl CustomHDPath {
             Ok(CustomHDPath(values))
         }
     }
+
+    /// Parse HD Path with the specified options, for example to accept a non-canonical format.
+    /// `FromStr` is same as parsing with the default options.
+    ///
+    /// See [`ParseOptions`](struct.ParseOptions.html)
+    pub fn parse_with(value: &str, options: &ParseOptions) -> Result<CustomHDPath, Error> {
+        parser::parse(value, options)
+    }
+
+    /// Decode from bytes, where first byte is number of elements in path following by 4-byte BE values,
+    /// i.e. the format produced by [`HDPath::to_bytes`](trait.HDPath.html#method.to_bytes)
+    pub fn from_bytes(path: &[u8]) -> Result<Self, Error> {
+        let len = match path.first() {
+            Some(len) => *len as usize,
+            None => return Err(Error::InvalidFormat)
+        };
+        if path.len() != 1 + 4 * len {
+            return Err(Error::InvalidFormat);
+        }
+        let values = path[1..].chunks(4)
+            .map(|value| PathValue::from_raw(u32::from_be_bytes(value.try_into().unwrap())))
+            .collect();
+        Ok(CustomHDPath(values))
+    }
 }
 
 impl HDPath for CustomHDPath {
@@ -55,7 +80,7 @@ impl HDPath for CustomHDPath {
     }
 
     fn get(&self, pos: u8) -> Option<PathValue> {
-        self.0.get(pos as usize).map(|a| a.clone())
+        self.0.get(pos as usize).cloned()
     }
 }
 
@@ -91,77 +116,7 @@ impl FromStr for CustomHDPath {
     type Err = Error;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        const STATE_EXPECT_NUM: usize = 0;
-        const STATE_READING_NUM: usize = 1;
-        const STATE_READ_MARKER: usize = 2;
-
-        let chars = value.as_bytes();
-        if chars.len() < 2 {
-            return Err(Error::InvalidFormat)
-        }
-        if chars[0] != 'm' as u8 && chars[0] != 'M' as u8 {
-            return Err(Error::InvalidFormat)
-        }
-        if chars[1] != '/' as u8 {
-            return Err(Error::InvalidFormat)
-        }
-        let mut keys: Vec<PathValue> = Vec::new();
-        let mut pos = 2;
-        let mut num: u32 = 0;
-        let mut state = STATE_EXPECT_NUM;
-        while chars.len() > pos {
-            match chars[pos] {
-                39 | 72 => { // (') apostrophe or H
-                    if state != STATE_READING_NUM {
-                        return Err(Error::InvalidFormat)
-                    }
-                    if !PathValue::is_ok(num) {
-                        return Err(Error::InvalidFormat)
-                    }
-                    keys.push(PathValue::hardened(num));
-                    state = STATE_READ_MARKER;
-                    num = 0;
-                },
-                47 => { // slash
-                    if state == STATE_READING_NUM {
-                        if !PathValue::is_ok(num) {
-                            return Err(Error::InvalidFormat)
-                        }
-                        keys.push(PathValue::normal(num));
-                    } else if state != STATE_READ_MARKER {
-                        return Err(Error::InvalidFormat)
-                    }
-                    state = STATE_EXPECT_NUM;
-                    num = 0;
-                },
-                48..=57 => { //number
-                    if state == STATE_EXPECT_NUM {
-                        state = STATE_READING_NUM
-                    } else if state != STATE_READING_NUM {
-                        return Err(Error::InvalidFormat)
-                    }
-                    num = num * 10 + (chars[pos] - 48) as u32;
-                },
-                _ => {
-                    return Err(Error::InvalidFormat)
-                }
-            }
-            pos += 1;
-            if chars.len() == pos && state == 1 {
-                if !PathValue::is_ok(num) {
-                    return Err(Error::InvalidFormat)
-                }
-                keys.push(PathValue::normal(num));
-            }
-        }
-        if state == STATE_EXPECT_NUM {
-            //finished with slash
-            Err(Error::InvalidFormat)
-        } else if keys.is_empty() {
-            Err(Error::InvalidStructure)
-        } else {
-            Ok(CustomHDPath(keys))
-        }
+        CustomHDPath::parse_with(value, &ParseOptions::default())
     }
 }
 
@@ -172,7 +127,7 @@ impl std::convert::From<&CustomHDPath> for Vec<ChildNumber> {
         for item in value.0.iter() {
             result.push(ChildNumber::from(item.to_raw()))
         }
-        return result;
+        result
     }
 }
 
@@ -197,11 +152,42 @@ impl std::convert::From<&CustomHDPath> for DerivationPath {
     }
 }
 
+/// Fails with `Error::InvalidLength` if the path is longer than 255 elements (see
+/// [`try_new`](struct.CustomHDPath.html#method.try_new))
+#[cfg(feature = "with-bitcoin")]
+impl TryFrom<&DerivationPath> for CustomHDPath {
+    type Error = Error;
+
+    fn try_from(value: &DerivationPath) -> Result<Self, Self::Error> {
+        CustomHDPath::try_new(value.into_iter().map(|c| PathValue::from(*c)).collect())
+    }
+}
+
+#[cfg(feature = "with-bitcoin")]
+impl TryFrom<DerivationPath> for CustomHDPath {
+    type Error = Error;
+
+    fn try_from(value: DerivationPath) -> Result<Self, Self::Error> {
+        CustomHDPath::try_from(&value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::StandardHDPath;
 
+    #[test]
+    pub fn convert_bytes() {
+        let hd_path = CustomHDPath::try_from("m/44'/0'/0'/0/0/1'").unwrap();
+        let bytes = hd_path.to_bytes();
+        assert_eq!(hd_path, CustomHDPath::from_bytes(&bytes).unwrap());
+        assert_eq!(CustomHDPath(vec![]), CustomHDPath::from_bytes(&[0]).unwrap());
+        assert_eq!(Err(Error::InvalidFormat), CustomHDPath::from_bytes(&[]));
+        assert_eq!(Err(Error::InvalidFormat), CustomHDPath::from_bytes(&bytes[..bytes.len() - 1]));
+        assert_eq!(Err(Error::InvalidFormat), CustomHDPath::from_bytes(&[1, 0, 0, 0, 1, 0]));
+    }
+
     #[test]
     pub fn to_string() {
         assert_eq!(
@@ -230,7 +216,7 @@ mod tests {
         let act = CustomHDPath::try_from("m/44'/0'/0'/0/0").unwrap();
         act.0[0].as_number();
         assert_eq!(5, act.0.len());
-        assert_eq!(&PathValue::Hardened(44), act.0.get(0).unwrap());
+        assert_eq!(&PathValue::Hardened(44), act.0.first().unwrap());
         assert_eq!(&PathValue::Hardened(0), act.0.get(1).unwrap());
         assert_eq!(&PathValue::Hardened(0), act.0.get(2).unwrap());
         assert_eq!(&PathValue::Normal(0), act.0.get(3).unwrap());
@@ -250,7 +236,7 @@ mod tests {
     pub fn try_from_bignum() {
         let act = CustomHDPath::try_from("m/44'/12'/345'/6789/101112").unwrap();
         assert_eq!(5, act.0.len());
-        assert_eq!(&PathValue::Hardened(44), act.0.get(0).unwrap());
+        assert_eq!(&PathValue::Hardened(44), act.0.first().unwrap());
         assert_eq!(&PathValue::Hardened(12), act.0.get(1).unwrap());
         assert_eq!(&PathValue::Hardened(345), act.0.get(2).unwrap());
         assert_eq!(&PathValue::Normal(6789), act.0.get(3).unwrap());
@@ -261,7 +247,7 @@ mod tests {
     pub fn try_from_long() {
         let act = CustomHDPath::try_from("m/44'/0'/1'/2/3/4'/5/67'/8'/910").unwrap();
         assert_eq!(10, act.0.len());
-        assert_eq!(&PathValue::Hardened(44), act.0.get(0).unwrap());
+        assert_eq!(&PathValue::Hardened(44), act.0.first().unwrap());
         assert_eq!(&PathValue::Hardened(0), act.0.get(1).unwrap());
         assert_eq!(&PathValue::Hardened(1), act.0.get(2).unwrap());
         assert_eq!(&PathValue::Normal(2), act.0.get(3).unwrap());
@@ -277,7 +263,7 @@ mod tests {
     pub fn try_from_all_hardened() {
         let act = CustomHDPath::try_from("m/44'/0'/0'/0'/1'").unwrap();
         assert_eq!(5, act.0.len());
-        assert_eq!(&PathValue::Hardened(44), act.0.get(0).unwrap());
+        assert_eq!(&PathValue::Hardened(44), act.0.first().unwrap());
         assert_eq!(&PathValue::Hardened(0), act.0.get(1).unwrap());
         assert_eq!(&PathValue::Hardened(0), act.0.get(2).unwrap());
         assert_eq!(&PathValue::Hardened(0), act.0.get(3).unwrap());
@@ -288,7 +274,7 @@ mod tests {
     pub fn try_from_all_normal() {
         let act = CustomHDPath::try_from("m/44/0/0/0/1").unwrap();
         assert_eq!(5, act.0.len());
-        assert_eq!(&PathValue::Normal(44), act.0.get(0).unwrap());
+        assert_eq!(&PathValue::Normal(44), act.0.first().unwrap());
         assert_eq!(&PathValue::Normal(0), act.0.get(1).unwrap());
         assert_eq!(&PathValue::Normal(0), act.0.get(2).unwrap());
         assert_eq!(&PathValue::Normal(0), act.0.get(3).unwrap());
@@ -299,7 +285,7 @@ mod tests {
     pub fn try_from_other_format() {
         let act = CustomHDPath::try_from("M/44H/0H/0H/1/5").unwrap();
         assert_eq!(5, act.0.len());
-        assert_eq!(&PathValue::Hardened(44), act.0.get(0).unwrap());
+        assert_eq!(&PathValue::Hardened(44), act.0.first().unwrap());
         assert_eq!(&PathValue::Hardened(0), act.0.get(1).unwrap());
         assert_eq!(&PathValue::Hardened(0), act.0.get(2).unwrap());
         assert_eq!(&PathValue::Normal(1), act.0.get(3).unwrap());
@@ -355,4 +341,25 @@ mod tests_with_bitcoin {
         assert_eq!(childs[6], ChildNumber::from_normal_idx(0).unwrap());
     }
 
-}
\ No newline at end of file
+    #[test]
+    pub fn convert_from_bitcoin() {
+        let source = DerivationPath::from_str("m/44'/15'/2'/0/35/81/0").unwrap();
+        let act = CustomHDPath::try_from(&source).unwrap();
+        assert_eq!(CustomHDPath::try_from("m/44'/15'/2'/0/35/81/0").unwrap(), act);
+        assert_eq!(source, DerivationPath::from(act));
+    }
+
+    #[test]
+    pub fn convert_from_bitcoin_master() {
+        let act = CustomHDPath::try_from(DerivationPath::master()).unwrap();
+        assert_eq!(0, act.len());
+    }
+
+    #[test]
+    pub fn convert_from_bitcoin_too_long() {
+        let source = DerivationPath::from(vec![ChildNumber::from_normal_idx(1).unwrap(); 256]);
+        assert_eq!(Err(Error::InvalidLength(256)), CustomHDPath::try_from(&source));
+        let source = DerivationPath::from(vec![ChildNumber::from_normal_idx(1).unwrap(); 255]);
+        assert_eq!(255, CustomHDPath::try_from(source).unwrap().len());
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{CustomHDPath, Error, PathValue};
+use crate::traits::HDPath;
+use sha2::{Digest, Sha256};
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::str::FromStr;
+
+/// Purpose of SLIP-13 identity paths
+pub const IDENTITY_PURPOSE: u32 = 13;
+
+/// Identity for [SLIP-13](https://github.com/satoshilabs/slips/blob/master/slip-0013.md), as parts of a URI
+/// `proto://user@host:port/path`, where only the host is required.
+///
+/// # Usage
+/// ```
+/// use hdpath::Identity;
+/// # use std::str::FromStr;
+///
+/// let identity = Identity::from_str("ssh://git@github.com:22").unwrap();
+/// assert_eq!(Some("git".to_string()), identity.user);
+/// assert_eq!("ssh://git@github.com:22", identity.to_string());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+pub struct Identity {
+    pub proto: Option<String>,
+    pub user: Option<String>,
+    pub host: String,
+    pub port: Option<String>,
+    /// Path, including the leading `/`
+    pub path: Option<String>,
+}
+
+impl FromStr for Identity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (proto, rest) = match s.split_once("://") {
+            Some((proto, rest)) => (Some(proto.to_string()), rest),
+            None => (None, s),
+        };
+        let (rest, path) = match rest.find('/') {
+            Some(pos) => (&rest[..pos], Some(rest[pos..].to_string())),
+            None => (rest, None),
+        };
+        let (user, rest) = match rest.rsplit_once('@') {
+            Some((user, rest)) => (Some(user.to_string()), rest),
+            None => (None, rest),
+        };
+        let (host, port) = match rest.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port.to_string())),
+            None => (rest, None),
+        };
+        if host.is_empty() {
+            return Err(Error::InvalidFormat)
+        }
+        Ok(Identity { proto, user, host: host.to_string(), port, path })
+    }
+}
+
+impl fmt::Display for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(proto) = &self.proto {
+            write!(f, "{}://", proto)?;
+        }
+        if let Some(user) = &self.user {
+            write!(f, "{}@", user)?;
+        }
+        write!(f, "{}", self.host)?;
+        if let Some(port) = &self.port {
+            write!(f, ":{}", port)?;
+        }
+        if let Some(path) = &self.path {
+            write!(f, "{}", path)?;
+        }
+        Ok(())
+    }
+}
+
+/// HD Path for an identity, `m/13'/a'/b'/c'/d'` as defined by [SLIP-13](https://github.com/satoshilabs/slips/blob/master/slip-0013.md),
+/// where the values are taken from SHA-256 hash of the index and the identity URI.
+///
+/// # Usage
+/// ```
+/// use hdpath::IdentityHDPath;
+///
+/// let hd_path = IdentityHDPath::from_uri(0, "https://satoshi@bitcoin.org/login");
+/// assert_eq!("m/13'/490267344'/697598796'/1613620211'/1858012177'", hd_path.to_string());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct IdentityHDPath {
+    values: [u32; 4],
+}
+
+impl IdentityHDPath {
+
+    /// Path for the identity URI, where `index` allows to have multiple keys for the same identity
+    pub fn from_uri(index: u32, uri: &str) -> IdentityHDPath {
+        let mut hasher = Sha256::new();
+        hasher.update(index.to_le_bytes());
+        hasher.update(uri.as_bytes());
+        let hash = hasher.finalize();
+        let mut values = [0u32; 4];
+        for (i, value) in values.iter_mut().enumerate() {
+            let raw = u32::from_le_bytes(hash[i * 4..i * 4 + 4].try_into().unwrap());
+            *value = raw & 0x7fffffff;
+        }
+        IdentityHDPath { values }
+    }
+
+    /// Path for the identity, same as `from_uri` with the URI of the identity
+    pub fn from_identity(index: u32, identity: &Identity) -> IdentityHDPath {
+        IdentityHDPath::from_uri(index, &identity.to_string())
+    }
+
+    /// Values of `a'`, `b'`, `c'` and `d'`, without the hardened bit
+    pub fn values(&self) -> [u32; 4] {
+        self.values
+    }
+}
+
+impl HDPath for IdentityHDPath {
+    fn len(&self) -> u8 {
+        5
+    }
+
+    fn get(&self, pos: u8) -> Option<PathValue> {
+        match pos {
+            0 => Some(PathValue::Hardened(IDENTITY_PURPOSE)),
+            1..=4 => Some(PathValue::Hardened(self.values[pos as usize - 1])),
+            _ => None
+        }
+    }
+}
+
+impl TryFrom<CustomHDPath> for IdentityHDPath {
+    type Error = Error;
+
+    fn try_from(value: CustomHDPath) -> Result<Self, Self::Error> {
+        if value.0.len() != 5 {
+            return Err(Error::InvalidLength(value.0.len()))
+        }
+        if value.0[0] != PathValue::Hardened(IDENTITY_PURPOSE) {
+            return Err(Error::InvalidPurpose(value.0[0].as_number()))
+        }
+        let mut values = [0u32; 4];
+        for (i, value) in value.0[1..].iter().enumerate() {
+            match value {
+                PathValue::Hardened(n) => values[i] = *n,
+                PathValue::Normal(_) => return Err(Error::InvalidStructure)
+            }
+        }
+        Ok(IdentityHDPath { values })
+    }
+}
+
+impl FromStr for IdentityHDPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = CustomHDPath::from_str(s)?;
+        IdentityHDPath::try_from(value)
+    }
+}
+
+impl fmt::Display for IdentityHDPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m/{}'/{}'/{}'/{}'/{}'",
+               IDENTITY_PURPOSE,
+               self.values[0],
+               self.values[1],
+               self.values[2],
+               self.values[3]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(hd_path: &IdentityHDPath) -> Vec<u32> {
+        hd_path.as_custom().0.iter().map(|v| v.to_raw()).collect()
+    }
+
+    #[test]
+    pub fn slip13_vector() {
+        // https://github.com/satoshilabs/slips/blob/master/slip-0013.md#example
+        let hd_path = IdentityHDPath::from_uri(0, "https://satoshi@bitcoin.org/login");
+        assert_eq!(vec![2147483661, 2637750992, 2845082444, 3761103859, 4005495825], raw(&hd_path));
+    }
+
+    #[test]
+    pub fn from_identity() {
+        let identity = Identity {
+            proto: Some("https".to_string()),
+            user: Some("satoshi".to_string()),
+            host: "bitcoin.org".to_string(),
+            port: None,
+            path: Some("/login".to_string()),
+        };
+        assert_eq!(IdentityHDPath::from_uri(0, "https://satoshi@bitcoin.org/login"), IdentityHDPath::from_identity(0, &identity));
+    }
+
+    #[test]
+    pub fn other_uris() {
+        assert_eq!(
+            vec![2147483661, 2887660002, 2911240370, 2711319212, 3626421133],
+            raw(&IdentityHDPath::from_uri(3, "ssh://user@host"))
+        );
+        assert_eq!(
+            vec![2147483661, 3584575150, 2653634105, 3310174367, 4230235603],
+            raw(&IdentityHDPath::from_uri(0, "gpg://alice@example.com:2222"))
+        );
+        assert_ne!(IdentityHDPath::from_uri(0, "ssh://user@host"), IdentityHDPath::from_uri(1, "ssh://user@host"));
+    }
+
+    #[test]
+    pub fn parse_and_display() {
+        let hd_path = IdentityHDPath::from_uri(0, "https://satoshi@bitcoin.org/login");
+        let value = hd_path.to_string();
+        assert_eq!(hd_path, IdentityHDPath::from_str(&value).unwrap());
+        assert_eq!([490267344, 697598796, 1613620211, 1858012177], hd_path.values());
+    }
+
+    #[test]
+    pub fn err_parse_invalid() {
+        assert_eq!(Err(Error::InvalidPurpose(44)), IdentityHDPath::from_str("m/44'/1'/2'/3'/4'"));
+        assert_eq!(Err(Error::InvalidStructure), IdentityHDPath::from_str("m/13'/1'/2'/3'/4"));
+        assert_eq!(Err(Error::InvalidLength(4)), IdentityHDPath::from_str("m/13'/1'/2'/3'"));
+    }
+
+    #[test]
+    pub fn parse_identity() {
+        let identity = Identity::from_str("https://satoshi@bitcoin.org:443/login/page").unwrap();
+        assert_eq!(Identity {
+            proto: Some("https".to_string()),
+            user: Some("satoshi".to_string()),
+            host: "bitcoin.org".to_string(),
+            port: Some("443".to_string()),
+            path: Some("/login/page".to_string()),
+        }, identity);
+        assert_eq!("https://satoshi@bitcoin.org:443/login/page", identity.to_string());
+
+        let identity = Identity::from_str("example.com").unwrap();
+        assert_eq!(Identity { host: "example.com".to_string(), ..Identity::default() }, identity);
+        assert_eq!("example.com", identity.to_string());
+
+        let identity = Identity::from_str("ssh://user@host").unwrap();
+        assert_eq!("ssh://user@host", identity.to_string());
+    }
+
+    #[test]
+    pub fn err_parse_identity() {
+        assert_eq!(Err(Error::InvalidFormat), Identity::from_str(""));
+        assert_eq!(Err(Error::InvalidFormat), Identity::from_str("ssh://user@"));
+        assert_eq!(Err(Error::InvalidFormat), Identity::from_str("https:///login"));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{CustomHDPath, Error, PathValue, Purpose, StandardHDPath};
+use crate::traits::HDPath;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Key family of an LND key, see [keychain/derivation.go](https://github.com/lightningnetwork/lnd/blob/master/keychain/derivation.go)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum KeyFamily {
+    /// `0`, keys for 2-of-2 multisig funding outputs
+    MultiSig,
+    /// `1`, revocation base points
+    RevocationBase,
+    /// `2`, HTLC base points
+    HtlcBase,
+    /// `3`, payment base points
+    PaymentBase,
+    /// `4`, delay base points
+    DelayBase,
+    /// `5`, revocation root for shachain
+    RevocationRoot,
+    /// `6`, node identity key
+    NodeKey,
+    /// `7`, key for encryption of the static channel backups
+    StaticBackup,
+    /// `8`, watchtower session keys
+    TowerSession,
+    /// `9`, watchtower identity key
+    TowerId,
+    /// Any other family (i.e. `10` and above), used by external tools
+    Custom(u32),
+}
+
+impl KeyFamily {
+    pub const fn as_number(&self) -> u32 {
+        match self {
+            KeyFamily::MultiSig => 0,
+            KeyFamily::RevocationBase => 1,
+            KeyFamily::HtlcBase => 2,
+            KeyFamily::PaymentBase => 3,
+            KeyFamily::DelayBase => 4,
+            KeyFamily::RevocationRoot => 5,
+            KeyFamily::NodeKey => 6,
+            KeyFamily::StaticBackup => 7,
+            KeyFamily::TowerSession => 8,
+            KeyFamily::TowerId => 9,
+            KeyFamily::Custom(n) => *n,
+        }
+    }
+}
+
+impl From<u32> for KeyFamily {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => KeyFamily::MultiSig,
+            1 => KeyFamily::RevocationBase,
+            2 => KeyFamily::HtlcBase,
+            3 => KeyFamily::PaymentBase,
+            4 => KeyFamily::DelayBase,
+            5 => KeyFamily::RevocationRoot,
+            6 => KeyFamily::NodeKey,
+            7 => KeyFamily::StaticBackup,
+            8 => KeyFamily::TowerSession,
+            9 => KeyFamily::TowerId,
+            n => KeyFamily::Custom(n),
+        }
+    }
+}
+
+/// HD Path of an LND key, `m/1017'/coin_type'/key_family'/0/index`
+///
+/// # Usage
+/// ```
+/// use hdpath::{KeyFamily, LightningKeyPath};
+/// # use std::str::FromStr;
+///
+/// let hd_path = LightningKeyPath::from_str("m/1017'/0'/6'/0/0").unwrap();
+/// assert_eq!(KeyFamily::NodeKey, hd_path.key_family());
+///
+/// let hd_path = LightningKeyPath::new(0, KeyFamily::MultiSig, 5);
+/// assert_eq!("m/1017'/0'/0'/0/5", hd_path.to_string());
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct LightningKeyPath {
+    coin_type: u32,
+    key_family: KeyFamily,
+    index: u32,
+}
+
+impl LightningKeyPath {
+
+    /// Create a path. Panics if any of the values is incorrect
+    pub fn new(coin_type: u32, key_family: KeyFamily, index: u32) -> LightningKeyPath {
+        match Self::try_new(coin_type, key_family, index) {
+            Ok(path) => path,
+            Err(_) => panic!("Invalid Lightning key path: coin {}, family {}, index {}", coin_type, key_family.as_number(), index)
+        }
+    }
+
+    pub fn try_new(coin_type: u32, key_family: KeyFamily, index: u32) -> Result<LightningKeyPath, Error> {
+        if !PathValue::is_ok(coin_type) || !PathValue::is_ok(key_family.as_number()) || !PathValue::is_ok(index) {
+            return Err(Error::HighBitIsSet)
+        }
+        Ok(LightningKeyPath { coin_type, key_family, index })
+    }
+
+    pub fn coin_type(&self) -> u32 {
+        self.coin_type
+    }
+
+    pub fn key_family(&self) -> KeyFamily {
+        self.key_family
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl HDPath for LightningKeyPath {
+    fn len(&self) -> u8 {
+        5
+    }
+
+    fn get(&self, pos: u8) -> Option<PathValue> {
+        match pos {
+            0 => Some(Purpose::Lightning.as_value()),
+            1 => Some(PathValue::Hardened(self.coin_type)),
+            2 => Some(PathValue::Hardened(self.key_family.as_number())),
+            3 => Some(PathValue::Normal(0)),
+            4 => Some(PathValue::Normal(self.index)),
+            _ => None
+        }
+    }
+}
+
+impl TryFrom<CustomHDPath> for LightningKeyPath {
+    type Error = Error;
+
+    fn try_from(value: CustomHDPath) -> Result<Self, Self::Error> {
+        let standard = StandardHDPath::try_from(value)?;
+        LightningKeyPath::try_from(&standard)
+    }
+}
+
+impl TryFrom<&StandardHDPath> for LightningKeyPath {
+    type Error = Error;
+
+    fn try_from(value: &StandardHDPath) -> Result<Self, Self::Error> {
+        if *value.purpose() != Purpose::Lightning {
+            return Err(Error::InvalidPurpose(value.purpose().as_value().as_number()))
+        }
+        if value.change() != 0 {
+            return Err(Error::InvalidStructure)
+        }
+        LightningKeyPath::try_new(value.coin_type(), KeyFamily::from(value.account()), value.index())
+    }
+}
+
+impl From<&LightningKeyPath> for StandardHDPath {
+    fn from(value: &LightningKeyPath) -> Self {
+        StandardHDPath::new(Purpose::Lightning, value.coin_type, value.key_family.as_number(), 0, value.index)
+    }
+}
+
+impl TryFrom<&str> for LightningKeyPath {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        LightningKeyPath::from_str(value)
+    }
+}
+
+impl FromStr for LightningKeyPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = CustomHDPath::from_str(s)?;
+        LightningKeyPath::try_from(value)
+    }
+}
+
+impl fmt::Display for LightningKeyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m/{}'/{}'/{}'/0/{}",
+               Purpose::Lightning.as_value().as_number(),
+               self.coin_type,
+               self.key_family.as_number(),
+               self.index
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_and_display() {
+        let hd_path = LightningKeyPath::from_str("m/1017'/1'/7'/0/2").unwrap();
+        assert_eq!(1, hd_path.coin_type());
+        assert_eq!(KeyFamily::StaticBackup, hd_path.key_family());
+        assert_eq!(2, hd_path.index());
+        assert_eq!("m/1017'/1'/7'/0/2", hd_path.to_string());
+        assert_eq!("m/1017'/1'/7'/0/2", hd_path.as_custom().to_string());
+    }
+
+    #[test]
+    pub fn all_families() {
+        for n in 0..12u32 {
+            let family = KeyFamily::from(n);
+            assert_eq!(n, family.as_number());
+            let hd_path = LightningKeyPath::new(0, family, 0);
+            assert_eq!(hd_path, LightningKeyPath::from_str(&hd_path.to_string()).unwrap());
+        }
+        assert_eq!(KeyFamily::NodeKey, KeyFamily::from(6));
+        assert_eq!(KeyFamily::TowerId, KeyFamily::from(9));
+        assert_eq!(KeyFamily::Custom(10), KeyFamily::from(10));
+    }
+
+    #[test]
+    pub fn err_parse_invalid() {
+        assert_eq!(Err(Error::InvalidPurpose(44)), LightningKeyPath::from_str("m/44'/0'/6'/0/0"));
+        assert_eq!(Err(Error::InvalidStructure), LightningKeyPath::from_str("m/1017'/0'/6'/1/0"));
+        assert_eq!(Err(Error::InvalidStructure), LightningKeyPath::from_str("m/1017'/0'/6/0/0"));
+        assert_eq!(Err(Error::InvalidLength(4)), LightningKeyPath::from_str("m/1017'/0'/6'/0"));
+    }
+
+    #[test]
+    pub fn err_create_invalid() {
+        assert_eq!(Err(Error::HighBitIsSet), LightningKeyPath::try_new(0x80000000, KeyFamily::NodeKey, 0));
+        assert_eq!(Err(Error::HighBitIsSet), LightningKeyPath::try_new(0, KeyFamily::Custom(0x80000000), 0));
+        assert_eq!(Err(Error::HighBitIsSet), LightningKeyPath::try_new(0, KeyFamily::NodeKey, 0x80000000));
+    }
+
+    #[test]
+    pub fn convert_standard() {
+        let hd_path = LightningKeyPath::new(0, KeyFamily::NodeKey, 0);
+        let standard = StandardHDPath::from(&hd_path);
+        assert_eq!("m/1017'/0'/6'/0/0", standard.to_string());
+        assert_eq!(hd_path, LightningKeyPath::try_from(&standard).unwrap());
+    }
+}
@@ -0,0 +1,636 @@
+use crate::{CustomHDPath, Error, HDPath, PathValue};
+use std::collections::btree_map;
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use std::marker::PhantomData;
+#[cfg(feature = "serde")]
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Node<V> {
+    value: Option<V>,
+    children: BTreeMap<PathValue, Node<V>>,
+}
+
+impl<V> Node<V> {
+    fn new() -> Node<V> {
+        Node { value: None, children: BTreeMap::new() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.value.is_none() && self.children.is_empty()
+    }
+
+    fn find(&self, hd_path: &dyn HDPath) -> Option<&Node<V>> {
+        let mut node = self;
+        for i in 0..hd_path.len() {
+            node = node.children.get(&hd_path.get(i)?)?;
+        }
+        Some(node)
+    }
+
+    fn remove(&mut self, path: &[PathValue]) -> Option<V> {
+        match path.split_first() {
+            None => self.value.take(),
+            Some((first, rest)) => {
+                let child = self.children.get_mut(first)?;
+                let value = child.remove(rest);
+                if child.is_empty() {
+                    self.children.remove(first);
+                }
+                value
+            }
+        }
+    }
+}
+
+/// Map from HD Paths to values, stored as a trie keyed by the path elements. Makes it easy to find all paths under
+/// a particular account or chain, and the last index used on a chain.
+///
+/// The iteration order is the lexicographic order of path elements (a parent before its children, a normal
+/// value before hardened), which is the same as the `Ord` of [`StandardHDPath`](struct.StandardHDPath.html).
+///
+/// # Usage
+/// ```
+/// use hdpath::{PathMap, StandardHDPath, CustomHDPath};
+/// # use std::str::FromStr;
+///
+/// let mut balances = PathMap::new();
+/// balances.insert(&StandardHDPath::from_str("m/84'/0'/0'/0/0").unwrap(), 1000u64);
+/// balances.insert(&StandardHDPath::from_str("m/84'/0'/0'/0/4").unwrap(), 500u64);
+/// balances.insert(&StandardHDPath::from_str("m/84'/0'/1'/0/0").unwrap(), 10u64);
+///
+/// let account = CustomHDPath::from_str("m/84'/0'/0'").unwrap();
+/// let total: u64 = balances.iter_prefix(&account).map(|(_, v)| v).sum();
+/// assert_eq!(1500, total);
+///
+/// let chain = CustomHDPath::from_str("m/84'/0'/0'/0").unwrap();
+/// assert_eq!(Some(4), balances.max_index(&chain));
+/// assert_eq!(Some(5), balances.next_unused(&chain));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PathMap<V> {
+    root: Node<V>,
+    len: usize,
+}
+
+impl<V> Default for PathMap<V> {
+    fn default() -> Self {
+        PathMap::new()
+    }
+}
+
+impl<V> PathMap<V> {
+
+    pub fn new() -> PathMap<V> {
+        PathMap { root: Node::new(), len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Set value for the path, returns the previous value if it was set
+    pub fn insert(&mut self, hd_path: &dyn HDPath, value: V) -> Option<V> {
+        let mut node = &mut self.root;
+        for i in 0..hd_path.len() {
+            let key = hd_path.get(i).expect("value in path range");
+            node = node.children.entry(key).or_insert_with(Node::new);
+        }
+        let previous = node.value.replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    pub fn get(&self, hd_path: &dyn HDPath) -> Option<&V> {
+        self.root.find(hd_path).and_then(|node| node.value.as_ref())
+    }
+
+    pub fn get_mut(&mut self, hd_path: &dyn HDPath) -> Option<&mut V> {
+        let mut node = &mut self.root;
+        for i in 0..hd_path.len() {
+            node = node.children.get_mut(&hd_path.get(i)?)?;
+        }
+        node.value.as_mut()
+    }
+
+    pub fn contains(&self, hd_path: &dyn HDPath) -> bool {
+        self.get(hd_path).is_some()
+    }
+
+    pub fn remove(&mut self, hd_path: &dyn HDPath) -> Option<V> {
+        let value = self.root.remove(&hd_path.as_custom().0);
+        if value.is_some() {
+            self.len -= 1;
+        }
+        value
+    }
+
+    /// All paths with their values, in the order of path elements
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter::new(&self.root, vec![])
+    }
+
+    /// Paths with their values which are under the `prefix`, including the `prefix` path itself
+    pub fn iter_prefix(&self, prefix: &dyn HDPath) -> Iter<'_, V> {
+        match self.root.find(prefix) {
+            Some(node) => Iter::new(node, prefix.as_custom().0),
+            None => Iter::empty()
+        }
+    }
+
+    /// Max normal index used right under the `chain` path, e.g. for `m/84'/0'/0'/0` it's the last
+    /// used receive address index of the account
+    pub fn max_index(&self, chain: &dyn HDPath) -> Option<u32> {
+        self.root.find(chain)?
+            .children.keys()
+            .rev()
+            .find(|v| !v.is_hardened())
+            .map(|v| v.as_number())
+    }
+
+    /// Next index after the max index used on the `chain` (see [`max_index`](#method.max_index)), or `0` if the chain is empty.
+    /// Returns `None` if there are no more indexes available in the normal space.
+    pub fn next_unused(&self, chain: &dyn HDPath) -> Option<u32> {
+        match self.max_index(chain) {
+            Some(index) => {
+                let next = index + 1;
+                if PathValue::is_ok(next) { Some(next) } else { None }
+            }
+            None => Some(0)
+        }
+    }
+
+    /// Encode the map, with values encoded by `encode`. It's a 4-byte BE number of paths, and for each path:
+    /// path encoded with [`HDPath::to_bytes`](trait.HDPath.html#method.to_bytes),
+    /// 4-byte BE length of the value, the value.
+    pub fn to_bytes_with<F: Fn(&V) -> Vec<u8>>(&self, encode: F) -> Vec<u8> {
+        let mut buf = vec![];
+        buf.extend_from_slice(&(self.len as u32).to_be_bytes());
+        for (hd_path, value) in self.iter() {
+            let value = encode(value);
+            buf.extend_from_slice(&hd_path.to_bytes());
+            buf.extend_from_slice(&(value.len() as u32).to_be_bytes());
+            buf.extend_from_slice(&value);
+        }
+        buf
+    }
+
+    /// Decode the map encoded with [`to_bytes_with`](#method.to_bytes_with)
+    pub fn from_bytes_with<F: Fn(&[u8]) -> Result<V, Error>>(value: &[u8], decode: F) -> Result<PathMap<V>, Error> {
+        let mut result = PathMap::new();
+        let mut value = value;
+        let count = take_u32(&mut value)?;
+        for _ in 0..count {
+            let hd_path = take_path(&mut value)?;
+            let len = take_u32(&mut value)? as usize;
+            if value.len() < len {
+                return Err(Error::InvalidFormat)
+            }
+            let (item, rest) = value.split_at(len);
+            value = rest;
+            result.insert(&hd_path, decode(item)?);
+        }
+        if !value.is_empty() {
+            return Err(Error::InvalidFormat)
+        }
+        Ok(result)
+    }
+}
+
+fn take_u32(value: &mut &[u8]) -> Result<u32, Error> {
+    if value.len() < 4 {
+        return Err(Error::InvalidFormat)
+    }
+    let (n, rest) = value.split_at(4);
+    *value = rest;
+    Ok(u32::from_be_bytes(n.try_into().unwrap()))
+}
+
+fn take_path(value: &mut &[u8]) -> Result<CustomHDPath, Error> {
+    let len = 1 + 4 * (*value.first().ok_or(Error::InvalidFormat)? as usize);
+    if value.len() < len {
+        return Err(Error::InvalidFormat)
+    }
+    let (path, rest) = value.split_at(len);
+    *value = rest;
+    CustomHDPath::from_bytes(path)
+}
+
+/// Iterator over a [`PathMap`](struct.PathMap.html)
+pub struct Iter<'a, V> {
+    /// Value of the starting node, which is returned first
+    first: Option<&'a V>,
+    stack: Vec<btree_map::Iter<'a, PathValue, Node<V>>>,
+    path: Vec<PathValue>,
+    base: usize,
+}
+
+impl<'a, V> Iter<'a, V> {
+    fn new(node: &'a Node<V>, path: Vec<PathValue>) -> Iter<'a, V> {
+        Iter {
+            first: node.value.as_ref(),
+            stack: vec![node.children.iter()],
+            base: path.len(),
+            path,
+        }
+    }
+
+    fn empty() -> Iter<'a, V> {
+        Iter { first: None, stack: vec![], path: vec![], base: 0 }
+    }
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (CustomHDPath, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(value) = self.first.take() {
+            return Some((CustomHDPath(self.path.clone()), value))
+        }
+        while let Some(level) = self.stack.last_mut() {
+            match level.next() {
+                Some((key, node)) => {
+                    let depth = self.base + self.stack.len() - 1;
+                    self.path.truncate(depth);
+                    self.path.push(*key);
+                    self.stack.push(node.children.iter());
+                    if let Some(value) = node.value.as_ref() {
+                        return Some((CustomHDPath(self.path.clone()), value))
+                    }
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a, V> IntoIterator for &'a PathMap<V> {
+    type Item = (CustomHDPath, &'a V);
+    type IntoIter = Iter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Parses a serialized key, including the root `m` which `CustomHDPath::from_str` doesn't accept
+#[cfg(feature = "serde")]
+fn parse_key(value: &str) -> Result<CustomHDPath, Error> {
+    if value == "m" {
+        return Ok(CustomHDPath(vec![]))
+    }
+    CustomHDPath::from_str(value)
+}
+
+/// Serialized as a map from the path string to the value, e.g. `{"m/84'/0'/0'/0/0": 1000}`
+#[cfg(feature = "serde")]
+impl<V: Serialize> Serialize for PathMap<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter().map(|(k, v)| (k.to_string(), v)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for PathMap<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MapVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> serde::de::Visitor<'de> for MapVisitor<V> {
+            type Value = PathMap<V>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map from HD Path to value")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+                let mut result = PathMap::new();
+                while let Some((key, value)) = access.next_entry::<String, V>()? {
+                    let hd_path = parse_key(&key).map_err(serde::de::Error::custom)?;
+                    result.insert(&hd_path, value);
+                }
+                Ok(result)
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+}
+
+/// Set of HD Paths, see [`PathMap`](struct.PathMap.html)
+///
+/// # Usage
+/// ```
+/// use hdpath::{PathSet, StandardHDPath, CustomHDPath};
+/// # use std::str::FromStr;
+///
+/// let mut used = PathSet::new();
+/// used.insert(&StandardHDPath::from_str("m/84'/0'/0'/0/0").unwrap());
+/// used.insert(&StandardHDPath::from_str("m/84'/0'/0'/0/1").unwrap());
+///
+/// let chain = CustomHDPath::from_str("m/84'/0'/0'/0").unwrap();
+/// assert_eq!(Some(2), used.next_unused(&chain));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct PathSet(PathMap<()>);
+
+impl PathSet {
+
+    pub fn new() -> PathSet {
+        PathSet(PathMap::new())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add the path, returns `false` if it was already in the set
+    pub fn insert(&mut self, hd_path: &dyn HDPath) -> bool {
+        self.0.insert(hd_path, ()).is_none()
+    }
+
+    pub fn contains(&self, hd_path: &dyn HDPath) -> bool {
+        self.0.contains(hd_path)
+    }
+
+    /// Remove the path, returns `false` if it wasn't in the set
+    pub fn remove(&mut self, hd_path: &dyn HDPath) -> bool {
+        self.0.remove(hd_path).is_some()
+    }
+
+    /// All paths, in the order of path elements
+    pub fn iter(&self) -> impl Iterator<Item = CustomHDPath> + '_ {
+        self.0.iter().map(|(k, _)| k)
+    }
+
+    /// Paths which are under the `prefix`, including the `prefix` path itself
+    pub fn iter_prefix(&self, prefix: &dyn HDPath) -> impl Iterator<Item = CustomHDPath> + '_ {
+        self.0.iter_prefix(prefix).map(|(k, _)| k)
+    }
+
+    /// See [`PathMap::max_index`](struct.PathMap.html#method.max_index)
+    pub fn max_index(&self, chain: &dyn HDPath) -> Option<u32> {
+        self.0.max_index(chain)
+    }
+
+    /// See [`PathMap::next_unused`](struct.PathMap.html#method.next_unused)
+    pub fn next_unused(&self, chain: &dyn HDPath) -> Option<u32> {
+        self.0.next_unused(chain)
+    }
+
+    /// Encode the set as a 4-byte BE number of paths, followed by the paths encoded with [`HDPath::to_bytes`](trait.HDPath.html#method.to_bytes)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        buf.extend_from_slice(&(self.len() as u32).to_be_bytes());
+        for hd_path in self.iter() {
+            buf.extend_from_slice(&hd_path.to_bytes());
+        }
+        buf
+    }
+
+    /// Decode the set encoded with [`to_bytes`](#method.to_bytes)
+    pub fn from_bytes(value: &[u8]) -> Result<PathSet, Error> {
+        let mut result = PathSet::new();
+        let mut value = value;
+        let count = take_u32(&mut value)?;
+        for _ in 0..count {
+            result.insert(&take_path(&mut value)?);
+        }
+        if !value.is_empty() {
+            return Err(Error::InvalidFormat)
+        }
+        Ok(result)
+    }
+}
+
+/// Serialized as a list of path strings
+#[cfg(feature = "serde")]
+impl Serialize for PathSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(|p| p.to_string()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for PathSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<String>::deserialize(deserializer)?;
+        let mut result = PathSet::new();
+        for value in values {
+            let hd_path = parse_key(&value).map_err(serde::de::Error::custom)?;
+            result.insert(&hd_path);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountHDPath, Purpose, StandardHDPath};
+    #[cfg(not(target_arch = "wasm32"))]
+    use rand::{rng, Rng};
+    use std::str::FromStr;
+
+    fn path(value: &str) -> CustomHDPath {
+        CustomHDPath::from_str(value).unwrap()
+    }
+
+    fn strings(set: impl Iterator<Item = CustomHDPath>) -> Vec<String> {
+        set.map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    pub fn insert_and_get() {
+        let mut map = PathMap::new();
+        assert!(map.is_empty());
+        assert_eq!(None, map.insert(&path("m/84'/0'/0'/0/0"), 1));
+        assert_eq!(None, map.insert(&path("m/84'/0'/0'"), 2));
+        assert_eq!(Some(1), map.insert(&path("m/84'/0'/0'/0/0"), 3));
+        assert_eq!(2, map.len());
+        assert_eq!(Some(&3), map.get(&path("m/84'/0'/0'/0/0")));
+        assert_eq!(Some(&2), map.get(&AccountHDPath::from_str("m/84'/0'/0'").unwrap()));
+        assert_eq!(None, map.get(&path("m/84'/0'")));
+        assert_eq!(None, map.get(&path("m/84'/0'/0'/0/0'")));
+        assert!(!map.contains(&path("m/84'/0'/0'/0/1")));
+        *map.get_mut(&path("m/84'/0'/0'")).unwrap() += 10;
+        assert_eq!(Some(&12), map.get(&path("m/84'/0'/0'")));
+    }
+
+    #[test]
+    pub fn root_path() {
+        let mut map = PathMap::new();
+        let root = CustomHDPath(vec![]);
+        map.insert(&root, "root");
+        map.insert(&path("m/0"), "child");
+        assert_eq!(vec![(root, &"root"), (path("m/0"), &"child")], map.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    pub fn remove_prunes() {
+        let mut map = PathMap::new();
+        map.insert(&path("m/84'/0'/0'/0/0"), 1);
+        map.insert(&path("m/84'/0'/0'/0/1"), 2);
+        assert_eq!(None, map.remove(&path("m/84'/0'/0'/0")));
+        assert_eq!(Some(1), map.remove(&path("m/84'/0'/0'/0/0")));
+        assert_eq!(None, map.remove(&path("m/84'/0'/0'/0/0")));
+        assert_eq!(1, map.len());
+        assert_eq!(Some(2), map.remove(&path("m/84'/0'/0'/0/1")));
+        assert!(map.is_empty());
+        assert_eq!(PathMap::new(), map);
+    }
+
+    #[test]
+    pub fn iterate_prefix() {
+        let mut set = PathSet::new();
+        for p in ["m/84'/0'/1'/0/0", "m/84'/0'/0'/1/0", "m/84'/0'/0'/0/3", "m/84'/0'/0'", "m/44'/0'/0'/0/0", "m/84'/1'/0'/0/0"] {
+            set.insert(&path(p));
+        }
+        assert_eq!(
+            vec!["m/84'/0'/0'", "m/84'/0'/0'/0/3", "m/84'/0'/0'/1/0"],
+            strings(set.iter_prefix(&path("m/84'/0'/0'")))
+        );
+        assert_eq!(vec!["m/84'/0'/0'/1/0"], strings(set.iter_prefix(&path("m/84'/0'/0'/1"))));
+        assert!(set.iter_prefix(&path("m/49'")).next().is_none());
+        assert_eq!(6, set.iter_prefix(&CustomHDPath(vec![])).count());
+    }
+
+    #[test]
+    pub fn normal_before_hardened() {
+        let mut set = PathSet::new();
+        set.insert(&path("m/1'"));
+        set.insert(&path("m/2"));
+        set.insert(&path("m/1"));
+        set.insert(&path("m/0'/5"));
+        assert_eq!(4, set.len());
+        assert_eq!(vec!["m/1", "m/2", "m/0'/5", "m/1'"], strings(set.iter()));
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn order_same_as_standard() {
+        let mut random = rng();
+        let mut paths: Vec<StandardHDPath> = (0..500).map(|_| {
+            StandardHDPath::new(
+                [Purpose::Pubkey, Purpose::ScriptHash, Purpose::Witness, Purpose::Taproot][random.random_range(0..4)].clone(),
+                random.random_range(0..3),
+                random.random_range(0..3),
+                random.random_range(0..2),
+                random.random_range(0..50),
+            )
+        }).collect();
+        let mut set = PathSet::new();
+        for p in &paths {
+            set.insert(p);
+        }
+        paths.sort();
+        paths.dedup();
+        let expected: Vec<CustomHDPath> = paths.iter().map(|p| p.as_custom()).collect();
+        assert_eq!(expected, set.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    pub fn max_and_next_index() {
+        let mut set = PathSet::new();
+        let chain = path("m/84'/0'/0'/0");
+        assert_eq!(None, set.max_index(&chain));
+        assert_eq!(Some(0), set.next_unused(&chain));
+        set.insert(&path("m/84'/0'/0'/0/5"));
+        set.insert(&path("m/84'/0'/0'/0/2"));
+        set.insert(&path("m/84'/0'/0'/0/7'"));
+        set.insert(&path("m/84'/0'/0'/1/10"));
+        assert_eq!(Some(5), set.max_index(&chain));
+        assert_eq!(Some(6), set.next_unused(&chain));
+        assert_eq!(Some(10), set.max_index(&path("m/84'/0'/0'/1")));
+        set.insert(&path("m/84'/0'/0'/0/2147483647"));
+        assert_eq!(None, set.next_unused(&chain));
+    }
+
+    #[test]
+    pub fn encode_set() {
+        let mut set = PathSet::new();
+        set.insert(&path("m/84'/0'/0'/0/1"));
+        set.insert(&path("m/0"));
+        let bytes = set.to_bytes();
+        assert_eq!(
+            vec![0, 0, 0, 2,
+                 1, 0, 0, 0, 0,
+                 5, 0x80, 0, 0, 84, 0x80, 0, 0, 0, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            bytes
+        );
+        assert_eq!(set, PathSet::from_bytes(&bytes).unwrap());
+        assert!(PathSet::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(PathSet::from_bytes(&[]).is_err());
+    }
+
+    #[test]
+    pub fn encode_map() {
+        let mut map = PathMap::new();
+        map.insert(&path("m/84'/0'/0'/0/1"), 1000u64);
+        map.insert(&path("m/44'"), 5u64);
+        let bytes = map.to_bytes_with(|v| v.to_be_bytes().to_vec());
+        let decode = |v: &[u8]| v.try_into().map(u64::from_be_bytes).map_err(|_| Error::InvalidFormat);
+        assert_eq!(map, PathMap::from_bytes_with(&bytes, decode).unwrap());
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert!(PathMap::from_bytes_with(&extra, decode).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_with_serde {
+    use super::*;
+
+    #[test]
+    pub fn map_json() {
+        let mut map = PathMap::new();
+        map.insert(&CustomHDPath::from_str("m/84'/0'/0'/0/1").unwrap(), 1000u64);
+        map.insert(&CustomHDPath::from_str("m/44'/0'/0'/0/0").unwrap(), 5u64);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(r#"{"m/44'/0'/0'/0/0":5,"m/84'/0'/0'/0/1":1000}"#, json);
+        assert_eq!(map, serde_json::from_str(&json).unwrap());
+        assert!(serde_json::from_str::<PathMap<u64>>(r#"{"x/0": 1}"#).is_err());
+    }
+
+    #[test]
+    pub fn set_json() {
+        let mut set = PathSet::new();
+        set.insert(&CustomHDPath::from_str("m/84'/0'/0'/0/1").unwrap());
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(r#"["m/84'/0'/0'/0/1"]"#, json);
+        assert_eq!(set, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    pub fn root_json() {
+        let root = CustomHDPath(vec![]);
+        let mut map = PathMap::new();
+        map.insert(&root, 1u64);
+        map.insert(&CustomHDPath::from_str("m/0").unwrap(), 2u64);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(r#"{"m":1,"m/0":2}"#, json);
+        assert_eq!(map, serde_json::from_str(&json).unwrap());
+
+        let mut set = PathSet::new();
+        set.insert(&root);
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(r#"["m"]"#, json);
+        assert_eq!(set, serde_json::from_str(&json).unwrap());
+    }
+}
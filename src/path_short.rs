@@ -37,7 +37,7 @@ impl TryFrom<CustomHDPath> for ShortHDPath {
         if value.0.len() != 4 {
             return Err(Error::InvalidLength(value.0.len()))
         }
-        if let Some(PathValue::Hardened(p)) = value.0.get(0) {
+        if let Some(PathValue::Hardened(p)) = value.0.first() {
             let purpose = Purpose::try_from(*p)?;
             if let Some(PathValue::Hardened(coin_type)) = value.0.get(1) {
                 if let Some(PathValue::Hardened(account)) = value.0.get(2) {
@@ -100,7 +100,7 @@ impl std::convert::From<&ShortHDPath> for Vec<ChildNumber> {
             ChildNumber::from_normal_idx(value.index)
                 .expect("Index is Hardened"),
         ];
-        return result.to_vec();
+        result.to_vec()
     }
 }
 
@@ -125,6 +125,15 @@ impl std::convert::From<&ShortHDPath> for DerivationPath {
     }
 }
 
+#[cfg(feature = "with-bitcoin")]
+impl TryFrom<&DerivationPath> for ShortHDPath {
+    type Error = Error;
+
+    fn try_from(value: &DerivationPath) -> Result<Self, Self::Error> {
+        ShortHDPath::try_from(CustomHDPath::try_from(value)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,4 +191,22 @@ mod tests_with_bitcoin {
         assert_eq!(childs[3], ChildNumber::from_normal_idx(100).unwrap());
     }
 
-}
\ No newline at end of file
+    #[test]
+    pub fn convert_from_bitcoin() {
+        let source = DerivationPath::from_str("m/44'/60'/2'/100").unwrap();
+        let act = ShortHDPath::try_from(&source).unwrap();
+        assert_eq!(ShortHDPath::try_from("m/44'/60'/2'/100").unwrap(), act);
+    }
+
+    #[test]
+    pub fn err_convert_from_invalid_bitcoin() {
+        let paths = vec![
+            "m/44'/60'/2'", "m/44'/60'/2'/100/0",
+            "m/44/60'/2'/100", "m/44'/60'/2'/100'",
+        ];
+        for p in paths {
+            let source = DerivationPath::from_str(p).unwrap();
+            assert!(ShortHDPath::try_from(&source).is_err(), "path: {}", p);
+        }
+    }
+}
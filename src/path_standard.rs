@@ -88,6 +88,33 @@ impl StandardHDPath {
         })
     }
 
+    ///Try to create a standard HD Path in a const context, i.e. same as `try_new` but returns
+    ///`Error::HighBitIsSet` if a field has an incorrect value.
+    ///```
+    ///use hdpath::{StandardHDPath, Purpose};
+    ///
+    ///const HD_PATH: StandardHDPath = match StandardHDPath::try_new_const(Purpose::Witness, 0, 2, 0, 0) {
+    ///    Ok(hd_path) => hd_path,
+    ///    Err(_) => panic!("invalid path")
+    ///};
+    ///```
+    pub const fn try_new_const(purpose: Purpose, coin_type: u32, account: u32, change: u32, index: u32) -> Result<StandardHDPath, Error> {
+        if !PathValue::is_ok(purpose.as_value().as_number())
+            || !PathValue::is_ok(coin_type)
+            || !PathValue::is_ok(account)
+            || !PathValue::is_ok(change)
+            || !PathValue::is_ok(index) {
+            return Err(Error::HighBitIsSet);
+        }
+        Ok(StandardHDPath {
+            purpose,
+            coin_type,
+            account,
+            change,
+            index,
+        })
+    }
+
     pub fn purpose(&self) -> &Purpose {
         &self.purpose
     }
@@ -165,7 +192,7 @@ impl TryFrom<CustomHDPath> for StandardHDPath {
         if value.0.len() != 5 {
             return Err(Error::InvalidLength(value.0.len()))
         }
-        if let Some(PathValue::Hardened(p)) = value.0.get(0) {
+        if let Some(PathValue::Hardened(p)) = value.0.first() {
             let purpose = Purpose::try_from(*p)?;
             if let Some(PathValue::Hardened(coin_type)) = value.0.get(1) {
                 if let Some(PathValue::Hardened(account)) = value.0.get(2) {
@@ -248,7 +275,7 @@ impl std::convert::From<&StandardHDPath> for Vec<ChildNumber> {
             ChildNumber::from_normal_idx(value.index())
                 .expect("Index is Hardened"),
         ];
-        return result.to_vec();
+        result.to_vec()
     }
 }
 
@@ -273,10 +300,20 @@ impl std::convert::From<&StandardHDPath> for DerivationPath {
     }
 }
 
+#[cfg(feature = "with-bitcoin")]
+impl TryFrom<&DerivationPath> for StandardHDPath {
+    type Error = Error;
+
+    fn try_from(value: &DerivationPath) -> Result<Self, Self::Error> {
+        StandardHDPath::try_from(CustomHDPath::try_from(value)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::convert::TryFrom;
+    #[cfg(not(target_arch = "wasm32"))]
     use rand::{Rng};
 
     #[test]
@@ -309,7 +346,7 @@ mod tests {
         let standard = StandardHDPath::from_str("m/49'/0'/1'/0/5").unwrap();
         let act = CustomHDPath::from(standard);
         assert_eq!(5, act.0.len());
-        assert_eq!(&PathValue::Hardened(49), act.0.get(0).unwrap());
+        assert_eq!(&PathValue::Hardened(49), act.0.first().unwrap());
         assert_eq!(&PathValue::Hardened(0), act.0.get(1).unwrap());
         assert_eq!(&PathValue::Hardened(1), act.0.get(2).unwrap());
         assert_eq!(&PathValue::Normal(0), act.0.get(3).unwrap());
@@ -321,7 +358,7 @@ mod tests {
         let standard = StandardHDPath::try_from("m/49'/0'/1'/0/5").unwrap();
         let act = CustomHDPath::from(standard);
         assert_eq!(5, act.0.len());
-        assert_eq!(&PathValue::Hardened(49), act.0.get(0).unwrap());
+        assert_eq!(&PathValue::Hardened(49), act.0.first().unwrap());
         assert_eq!(&PathValue::Hardened(0), act.0.get(1).unwrap());
         assert_eq!(&PathValue::Hardened(1), act.0.get(2).unwrap());
         assert_eq!(&PathValue::Normal(0), act.0.get(3).unwrap());
@@ -346,7 +383,7 @@ mod tests {
             "m/49/0/1'/0/5",
         ];
         for p in paths {
-            let custom = CustomHDPath::try_from(p).expect(format!("failed for: {}", p).as_str());
+            let custom = CustomHDPath::try_from(p).unwrap_or_else(|_| panic!("failed for: {}", p));
             assert!(StandardHDPath::try_from(custom).is_err(), "test: {}", p);
         }
     }
@@ -638,6 +675,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn test_random_conversion() {
         let range = |count: usize| {
             let mut rng = rand::rng();
@@ -690,4 +728,23 @@ mod tests_with_bitcoin {
         assert_eq!(children[4], ChildNumber::from_normal_idx(3581).unwrap());
     }
 
-}
\ No newline at end of file
+    #[test]
+    pub fn convert_from_bitcoin() {
+        let source = DerivationPath::from_str("m/84'/0'/2'/1/35").unwrap();
+        let act = StandardHDPath::try_from(&source).unwrap();
+        assert_eq!(StandardHDPath::new(Purpose::Witness, 0, 2, 1, 35), act);
+    }
+
+    #[test]
+    pub fn err_convert_from_invalid_bitcoin() {
+        let paths = vec![
+            "m/84'/0'/2'/1", "m/84'/0'/2'/1/35/0",
+            "m/84/0'/2'/1/35", "m/84'/0/2'/1/35", "m/84'/0'/2/1/35",
+            "m/84'/0'/2'/1'/35", "m/84'/0'/2'/1/35'",
+        ];
+        for p in paths {
+            let source = DerivationPath::from_str(p).unwrap();
+            assert!(StandardHDPath::try_from(&source).is_err(), "path: {}", p);
+        }
+    }
+}
@@ -3,18 +3,19 @@ use bitcoin::bip32::ChildNumber;
 
 pub const FIRST_BIT: u32 = 0x80000000;
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum PathValue {
     Normal(u32),
     Hardened(u32),
 }
 
 impl PathValue {
-    pub fn is_ok(value: u32) -> bool {
+    pub const fn is_ok(value: u32) -> bool {
         value < FIRST_BIT
     }
 
-    pub fn try_normal(value: u32) -> Result<PathValue, ()> {
+    #[allow(clippy::result_unit_err)]
+    pub const fn try_normal(value: u32) -> Result<PathValue, ()> {
         if !PathValue::is_ok(value) {
             Err(())
         } else {
@@ -22,7 +23,7 @@ impl PathValue {
         }
     }
 
-    pub fn normal(value: u32) -> PathValue {
+    pub const fn normal(value: u32) -> PathValue {
         if let Ok(result) = PathValue::try_normal(value) {
             result
         } else {
@@ -30,7 +31,8 @@ impl PathValue {
         }
     }
 
-    pub fn try_hardened(value: u32) -> Result<PathValue, ()> {
+    #[allow(clippy::result_unit_err)]
+    pub const fn try_hardened(value: u32) -> Result<PathValue, ()> {
         if !PathValue::is_ok(value) {
             Err(())
         } else {
@@ -38,7 +40,7 @@ impl PathValue {
         }
     }
 
-    pub fn hardened(value: u32) -> PathValue {
+    pub const fn hardened(value: u32) -> PathValue {
         if let Ok(result) = PathValue::try_hardened(value) {
             result
         } else {
@@ -46,7 +48,7 @@ impl PathValue {
         }
     }
 
-    pub fn from_raw(value: u32) -> PathValue {
+    pub const fn from_raw(value: u32) -> PathValue {
         if value >= FIRST_BIT {
             PathValue::Hardened(value - FIRST_BIT)
         } else {
@@ -54,15 +56,19 @@ impl PathValue {
         }
     }
 
-    pub fn as_number(&self) -> u32 {
-        match &self {
+    pub const fn as_number(&self) -> u32 {
+        match self {
             PathValue::Normal(n) => *n,
             PathValue::Hardened(n) => *n
         }
     }
 
-    pub fn to_raw(&self) -> u32 {
-        match &self {
+    pub const fn is_hardened(&self) -> bool {
+        matches!(self, PathValue::Hardened(_))
+    }
+
+    pub const fn to_raw(&self) -> u32 {
+        match self {
             PathValue::Normal(n) => *n,
             PathValue::Hardened(n) => *n + FIRST_BIT
         }
@@ -88,6 +94,16 @@ impl std::fmt::Display for PathValue {
     }
 }
 
+#[cfg(feature = "with-bitcoin")]
+impl From<ChildNumber> for PathValue {
+    fn from(value: ChildNumber) -> Self {
+        match value {
+            ChildNumber::Hardened { index } => PathValue::Hardened(index),
+            ChildNumber::Normal { index } => PathValue::Normal(index),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,6 +132,16 @@ mod tests {
         assert_eq!(ChildNumber::from_hardened_idx(11).unwrap(), act);
     }
 
+    #[test]
+    #[cfg(feature = "with-bitcoin")]
+    fn convert_from_bitcoin() {
+        assert_eq!(PathValue::Normal(0), PathValue::from(ChildNumber::from_normal_idx(0).unwrap()));
+        assert_eq!(PathValue::Normal(100), PathValue::from(ChildNumber::from_normal_idx(100).unwrap()));
+        assert_eq!(PathValue::Hardened(0), PathValue::from(ChildNumber::from_hardened_idx(0).unwrap()));
+        assert_eq!(PathValue::Hardened(44), PathValue::from(ChildNumber::from_hardened_idx(44).unwrap()));
+        assert_eq!(PathValue::Hardened(0x7fffffff), PathValue::from(ChildNumber::from(0xffffffff)));
+    }
+
     #[test]
     fn to_string_normal() {
         assert_eq!(PathValue::Normal(0).to_string(), "0");
@@ -215,6 +241,12 @@ mod tests {
         assert_eq!(0x8000002c, PathValue::Hardened(44).to_raw());
     }
 
+    #[test]
+    fn is_hardened() {
+        assert!(PathValue::Hardened(0).is_hardened());
+        assert!(!PathValue::Normal(0).is_hardened());
+    }
+
     #[test]
     fn as_number_normal() {
         assert_eq!(0, PathValue::Normal(0).as_number());
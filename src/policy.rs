@@ -0,0 +1,523 @@
+use crate::{Error, HDPath, PathValue};
+use std::fmt;
+use std::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "with-bitcoin")]
+use bitcoin::Psbt;
+#[cfg(feature = "with-bitcoin")]
+use crate::{PsbtKeyPath, PsbtLocation};
+
+/// Pattern for a single element of a [`PathTemplate`](struct.PathTemplate.html)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ElementPattern {
+    /// Exactly the value, like `84'` or `0`
+    Exact(PathValue),
+    /// Any value, `*` for normal or `*'` for hardened
+    Any { hardened: bool },
+    /// Value in the inclusive range, `{0..=5}` for `0` to `5`, or `{0..=5}'` for hardened
+    Range { from: u32, to: u32, hardened: bool },
+}
+
+impl ElementPattern {
+
+    /// Check if the value matches the pattern
+    pub fn matches(&self, value: &PathValue) -> bool {
+        match self {
+            ElementPattern::Exact(expected) => expected == value,
+            ElementPattern::Any { hardened } => value.is_hardened() == *hardened,
+            ElementPattern::Range { from, to, hardened } => {
+                value.is_hardened() == *hardened && *from <= value.as_number() && value.as_number() <= *to
+            }
+        }
+    }
+}
+
+impl FromStr for ElementPattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (body, hardened) = match s.strip_suffix(|c| c == '\'' || c == 'h' || c == 'H') {
+            Some(body) => (body, true),
+            None => (s, false),
+        };
+        if body == "*" {
+            return Ok(ElementPattern::Any { hardened })
+        }
+        if let Some(range) = body.strip_prefix('{').and_then(|b| b.strip_suffix('}')) {
+            // a half-open `{0..5}` is rejected on purpose, because it's easy to misread as `0` to `5`; use `{0..=4}` instead
+            let (from, to) = range.split_once("..=").ok_or(Error::InvalidFormat)?;
+            let from = parse_number(from)?;
+            let to = parse_number(to)?;
+            if from > to {
+                return Err(Error::InvalidFormat)
+            }
+            return Ok(ElementPattern::Range { from, to, hardened })
+        }
+        let value = parse_number(body)?;
+        Ok(ElementPattern::Exact(if hardened { PathValue::Hardened(value) } else { PathValue::Normal(value) }))
+    }
+}
+
+fn parse_number(value: &str) -> Result<u32, Error> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::InvalidFormat)
+    }
+    let value = u32::from_str(value).map_err(|_| Error::InvalidFormat)?;
+    if !PathValue::is_ok(value) {
+        return Err(Error::HighBitIsSet)
+    }
+    Ok(value)
+}
+
+impl fmt::Display for ElementPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hardened = match self {
+            ElementPattern::Exact(value) => return write!(f, "{}", value),
+            ElementPattern::Any { hardened } => {
+                write!(f, "*")?;
+                *hardened
+            }
+            ElementPattern::Range { from, to, hardened } => {
+                write!(f, "{{{}..={}}}", from, to)?;
+                *hardened
+            }
+        };
+        if hardened {
+            write!(f, "'")?;
+        }
+        Ok(())
+    }
+}
+
+/// Template for a subtree of paths, like `m/84'/0'/{0..=4}'/*/*`. A path matches the template if each of the
+/// template elements matches the path element at the same position, i.e. the template matches the paths with the same
+/// length and all their descendants.
+///
+/// Elements of a template are:
+/// - an exact value, `84'` or `0`
+/// - `*` for any normal value, or `*'` for any hardened value
+/// - an inclusive range, `{0..=4}` (`0` to `4`), or `{0..=4}'` for a range of hardened values. A half-open range like `{0..5}`
+///   is rejected on purpose, since it's easy to misread as `0` to `5`; write it as `{0..=4}`
+///
+/// Hardened elements can be marked with `'`, `h` or `H`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PathTemplate(pub Vec<ElementPattern>);
+
+impl PathTemplate {
+
+    /// Check if the path is in the subtree defined by the template
+    pub fn matches(&self, hd_path: &dyn HDPath) -> bool {
+        if (hd_path.len() as usize) < self.0.len() {
+            return false
+        }
+        self.0.iter().enumerate().all(|(i, pattern)| {
+            match hd_path.get(i as u8) {
+                Some(value) => pattern.matches(&value),
+                None => false
+            }
+        })
+    }
+}
+
+impl FromStr for PathTemplate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('/');
+        if parts.next() != Some("m") {
+            return Err(Error::InvalidFormat)
+        }
+        let elements = parts.map(ElementPattern::from_str).collect::<Result<Vec<_>, Error>>()?;
+        if elements.len() > u8::MAX as usize {
+            return Err(Error::InvalidLength(elements.len()))
+        }
+        Ok(PathTemplate(elements))
+    }
+}
+
+impl fmt::Display for PathTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for element in &self.0 {
+            write!(f, "/{}", element)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for PathTemplate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for PathTemplate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        PathTemplate::from_str(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Result of checking a path with a [`PathPolicy`](struct.PathPolicy.html), which explains the decision
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Verdict {
+    /// The path is in the subtree of the allowed template
+    Allowed(PathTemplate),
+    /// The path is in the subtree of the denied template
+    Denied(PathTemplate),
+    /// The path doesn't match any of the allowed templates
+    NotAllowed,
+    /// There is no path to check, e.g. a PSBT input without any key origin
+    NoKeyOrigin,
+}
+
+impl Verdict {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Verdict::Allowed(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Allowed(template) => write!(f, "allowed by {}", template),
+            Verdict::Denied(template) => write!(f, "denied by {}", template),
+            Verdict::NotAllowed => write!(f, "not in any allowed path"),
+            Verdict::NoKeyOrigin => write!(f, "no key origin to check"),
+        }
+    }
+}
+
+/// Policy of permitted paths, as lists of allowed and denied templates (see [`PathTemplate`](struct.PathTemplate.html)).
+/// A path is permitted only if it matches one of the allowed templates and none of the denied templates.
+///
+/// The text format is a template per line, prefixed with `allow` or `deny`. Empty lines and lines starting with `#`
+/// are ignored. With `serde` feature it can be also loaded from a config as `{"allow": [...], "deny": [...]}`.
+///
+/// # Usage
+/// ```
+/// use hdpath::{PathPolicy, StandardHDPath};
+/// # use std::str::FromStr;
+///
+/// let policy = PathPolicy::from_str("
+///     allow m/84'/0'/{0..=4}'/*/*
+///     deny m/84'/0'/3'
+/// ").unwrap();
+///
+/// let hd_path = StandardHDPath::from_str("m/84'/0'/1'/0/7").unwrap();
+/// assert!(policy.check(&hd_path).is_allowed());
+///
+/// let hd_path = StandardHDPath::from_str("m/84'/0'/3'/0/7").unwrap();
+/// assert_eq!("denied by m/84'/0'/3'", policy.check(&hd_path).to_string());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PathPolicy {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub allow: Vec<PathTemplate>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub deny: Vec<PathTemplate>,
+}
+
+/// Result of checking multiple paths with a [`PathPolicy`](struct.PathPolicy.html)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PolicyReport {
+    /// Verdicts in the same order as the checked paths
+    pub verdicts: Vec<Verdict>,
+}
+
+impl PolicyReport {
+    /// `true` if all the paths are allowed. An empty report is not allowed, since nothing was checked
+    pub fn is_allowed(&self) -> bool {
+        !self.verdicts.is_empty() && self.verdicts.iter().all(|v| v.is_allowed())
+    }
+
+    /// Positions and verdicts of the paths that are not allowed
+    pub fn rejected(&self) -> impl Iterator<Item = (usize, &Verdict)> {
+        self.verdicts.iter().enumerate().filter(|(_, v)| !v.is_allowed())
+    }
+}
+
+impl PathPolicy {
+
+    /// Check if the path is permitted by the policy
+    pub fn check(&self, hd_path: &dyn HDPath) -> Verdict {
+        if let Some(template) = self.deny.iter().find(|t| t.matches(hd_path)) {
+            return Verdict::Denied(template.clone())
+        }
+        match self.allow.iter().find(|t| t.matches(hd_path)) {
+            Some(template) => Verdict::Allowed(template.clone()),
+            None => Verdict::NotAllowed
+        }
+    }
+
+    /// Check all the paths, e.g. all paths required to sign a transaction
+    pub fn check_all(&self, paths: &[&dyn HDPath]) -> PolicyReport {
+        PolicyReport {
+            verdicts: paths.iter().map(|p| self.check(*p)).collect()
+        }
+    }
+
+    /// Check all the key paths (both `bip32_derivation` and `tap_key_origins`) in the PSBT inputs and outputs, in the order of
+    /// [`PsbtKeyPath::all`](struct.PsbtKeyPath.html#method.all). An input without any key origin cannot be checked and gets
+    /// `Verdict::NoKeyOrigin` at its position instead. Fails if the PSBT contains a path that cannot be read
+    #[cfg(feature = "with-bitcoin")]
+    pub fn check_psbt(&self, psbt: &Psbt) -> Result<PolicyReport, Error> {
+        let keys = PsbtKeyPath::all(psbt)?;
+        let mut verdicts = Vec::with_capacity(keys.len());
+        for i in 0..psbt.inputs.len() {
+            let location = PsbtLocation::Input(i);
+            let before = verdicts.len();
+            verdicts.extend(keys.iter().filter(|k| k.location == location).map(|k| self.check(&k.path)));
+            if verdicts.len() == before {
+                verdicts.push(Verdict::NoKeyOrigin);
+            }
+        }
+        verdicts.extend(
+            keys.iter().filter(|k| matches!(k.location, PsbtLocation::Output(_))).map(|k| self.check(&k.path))
+        );
+        Ok(PolicyReport { verdicts })
+    }
+}
+
+impl FromStr for PathPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut policy = PathPolicy::default();
+        for line in s.lines().map(|l| l.trim()) {
+            if line.is_empty() || line.starts_with('#') {
+                continue
+            }
+            match line.split_once(char::is_whitespace) {
+                Some(("allow", template)) => policy.allow.push(PathTemplate::from_str(template)?),
+                Some(("deny", template)) => policy.deny.push(PathTemplate::from_str(template)?),
+                _ => return Err(Error::InvalidFormat)
+            }
+        }
+        Ok(policy)
+    }
+}
+
+impl fmt::Display for PathPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for template in &self.allow {
+            writeln!(f, "allow {}", template)?;
+        }
+        for template in &self.deny {
+            writeln!(f, "deny {}", template)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountHDPath, CustomHDPath, StandardHDPath};
+
+    fn path(value: &str) -> CustomHDPath {
+        CustomHDPath::from_str(value).unwrap()
+    }
+
+    #[test]
+    pub fn parse_template() {
+        let template = PathTemplate::from_str("m/84'/0'/{0..=4}'/*/*").unwrap();
+        assert_eq!(
+            PathTemplate(vec![
+                ElementPattern::Exact(PathValue::Hardened(84)),
+                ElementPattern::Exact(PathValue::Hardened(0)),
+                ElementPattern::Range { from: 0, to: 4, hardened: true },
+                ElementPattern::Any { hardened: false },
+                ElementPattern::Any { hardened: false },
+            ]),
+            template
+        );
+        assert_eq!("m/84'/0'/{0..=4}'/*/*", template.to_string());
+        assert_eq!(template, PathTemplate::from_str("m/84h/0h/{0..=4}h/*/*").unwrap());
+        assert_eq!(template, PathTemplate::from_str("m/84H/0H/{0..=4}H/*/*").unwrap());
+        assert_eq!(PathTemplate(vec![]), PathTemplate::from_str("m").unwrap());
+    }
+
+    #[test]
+    pub fn parse_invalid_template() {
+        assert!(PathTemplate::from_str("").is_err());
+        assert!(PathTemplate::from_str("84'/0'").is_err());
+        assert!(PathTemplate::from_str("m/84'/").is_err());
+        // half-open range is rejected, it must be written as `{0..=4}`
+        assert_eq!(Err(Error::InvalidFormat), PathTemplate::from_str("m/84'/0'/{0..5}'/*/*"));
+        assert!(PathTemplate::from_str("m/{0..=}").is_err());
+        assert!(PathTemplate::from_str("m/{5..=4}").is_err());
+        assert!(PathTemplate::from_str("m/{0..}").is_err());
+        assert!(PathTemplate::from_str("m/{0-5}").is_err());
+        assert!(PathTemplate::from_str("m/**").is_err());
+        assert!(PathTemplate::from_str("m/+1").is_err());
+        assert_eq!(Err(Error::HighBitIsSet), PathTemplate::from_str("m/2147483648"));
+    }
+
+    #[test]
+    pub fn match_subtree() {
+        let template = PathTemplate::from_str("m/84'/0'/{0..=4}'/*/*").unwrap();
+        assert!(template.matches(&path("m/84'/0'/0'/0/0")));
+        assert!(template.matches(&path("m/84'/0'/4'/1/100")));
+        assert!(template.matches(&path("m/84'/0'/4'/1/100/5")));
+        assert!(!template.matches(&path("m/84'/0'/5'/0/0")));
+        assert!(PathTemplate::from_str("m/{0..=5}").unwrap().matches(&path("m/5")));
+        assert!(!PathTemplate::from_str("m/{0..=5}").unwrap().matches(&path("m/6")));
+        assert!(PathTemplate::from_str("m/{5..=5}").unwrap().matches(&path("m/5")));
+        assert!(!template.matches(&path("m/84'/0'/0'/0'/0")));
+        assert!(!template.matches(&path("m/84'/0'/0'/0")));
+        assert!(!template.matches(&path("m/84'/0'/0/0/0")));
+        assert!(!template.matches(&path("m/44'/0'/0'/0/0")));
+
+        let template = PathTemplate::from_str("m/44'/*'").unwrap();
+        assert!(template.matches(&path("m/44'/60'/0'/0/0")));
+        assert!(!template.matches(&path("m/44'/60/0'/0/0")));
+        assert!(PathTemplate::from_str("m").unwrap().matches(&path("m/1/2")));
+    }
+
+    #[test]
+    pub fn check_paths() {
+        let policy = PathPolicy::from_str("
+            # main wallet
+            allow m/84'/0'/{0..=4}'/*/*
+            allow m/86'/0'/0'
+            deny m/84'/0'/3'
+        ").unwrap();
+        let allowed = PathTemplate::from_str("m/84'/0'/{0..=4}'/*/*").unwrap();
+        assert_eq!(Verdict::Allowed(allowed), policy.check(&StandardHDPath::from_str("m/84'/0'/0'/0/0").unwrap()));
+        assert_eq!(Verdict::NotAllowed, policy.check(&StandardHDPath::from_str("m/84'/0'/5'/0/0").unwrap()));
+        assert_eq!(Verdict::NotAllowed, policy.check(&AccountHDPath::from_str("m/84'/0'/1'").unwrap()));
+        assert_eq!(
+            Verdict::Denied(PathTemplate::from_str("m/84'/0'/3'").unwrap()),
+            policy.check(&StandardHDPath::from_str("m/84'/0'/3'/0/0").unwrap())
+        );
+        assert!(policy.check(&StandardHDPath::from_str("m/86'/0'/0'/1/9").unwrap()).is_allowed());
+        assert_eq!("not in any allowed path", Verdict::NotAllowed.to_string());
+    }
+
+    #[test]
+    pub fn check_all_paths() {
+        let policy = PathPolicy::from_str("allow m/84'/0'/0'").unwrap();
+        let a = path("m/84'/0'/0'/0/0");
+        let b = path("m/84'/0'/1'/0/0");
+        let c = path("m/84'/0'/0'/1/0");
+        let report = policy.check_all(&[&a, &b, &c]);
+        assert!(!report.is_allowed());
+        assert_eq!(vec![(1, &Verdict::NotAllowed)], report.rejected().collect::<Vec<_>>());
+        assert!(policy.check_all(&[&a, &c]).is_allowed());
+        assert!(!policy.check_all(&[]).is_allowed());
+    }
+
+    #[test]
+    pub fn empty_policy_denies() {
+        let policy = PathPolicy::default();
+        assert_eq!(Verdict::NotAllowed, policy.check(&path("m/84'/0'/0'/0/0")));
+    }
+
+    #[test]
+    pub fn parse_invalid_policy() {
+        assert!(PathPolicy::from_str("permit m/84'").is_err());
+        assert!(PathPolicy::from_str("allow").is_err());
+        assert!(PathPolicy::from_str("allow m/x").is_err());
+    }
+
+    #[test]
+    pub fn policy_display_and_parse() {
+        let policy = PathPolicy::from_str("deny m/84'/0'/3'\nallow m/84'/0'/{0..=4}'/*/*").unwrap();
+        assert_eq!("allow m/84'/0'/{0..=4}'/*/*\ndeny m/84'/0'/3'\n", policy.to_string());
+        assert_eq!(policy, PathPolicy::from_str(&policy.to_string()).unwrap());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_with_serde {
+    use super::*;
+
+    #[test]
+    pub fn load_json() {
+        let policy: PathPolicy = serde_json::from_str(r#"{"allow": ["m/84'/0'/{0..=4}'/*/*"], "deny": ["m/84'/0'/3'"]}"#).unwrap();
+        assert_eq!(PathPolicy::from_str("allow m/84'/0'/{0..=4}'/*/*\ndeny m/84'/0'/3'").unwrap(), policy);
+        let policy: PathPolicy = serde_json::from_str(r#"{"allow": ["m/84'"]}"#).unwrap();
+        assert!(policy.deny.is_empty());
+    }
+
+    #[test]
+    pub fn save_json() {
+        let policy = PathPolicy::from_str("allow m/84'/0'/{0..=4}'/*/*").unwrap();
+        assert_eq!(r#"{"allow":["m/84'/0'/{0..=4}'/*/*"],"deny":[]}"#, serde_json::to_string(&policy).unwrap());
+    }
+
+    #[test]
+    pub fn invalid_json() {
+        assert!(serde_json::from_str::<PathPolicy>(r#"{"allow": ["m/x"]}"#).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "with-bitcoin"))]
+mod tests_with_bitcoin {
+    use super::*;
+    use bitcoin::bip32::{DerivationPath, Fingerprint};
+    use bitcoin::secp256k1::PublicKey;
+    use bitcoin::{absolute, transaction, Transaction, TxIn};
+
+    #[test]
+    pub fn check_psbt_inputs() {
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn::default(), TxIn::default()],
+            output: vec![],
+        };
+        let mut psbt = Psbt::from_unsigned_tx(tx).unwrap();
+        let key = PublicKey::from_str("0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c").unwrap();
+        psbt.inputs[0].bip32_derivation.insert(key, (Fingerprint::default(), DerivationPath::from_str("m/84'/0'/0'/0/0").unwrap()));
+        psbt.inputs[1].bip32_derivation.insert(key, (Fingerprint::default(), DerivationPath::from_str("m/84'/0'/7'/0/0").unwrap()));
+
+        let policy = PathPolicy::from_str("allow m/84'/0'/{0..=4}'/*/*").unwrap();
+        let report = policy.check_psbt(&psbt).unwrap();
+        assert_eq!(2, report.verdicts.len());
+        assert!(report.verdicts[0].is_allowed());
+        assert_eq!(vec![(1, &Verdict::NotAllowed)], report.rejected().collect::<Vec<_>>());
+    }
+
+    #[test]
+    pub fn check_psbt_taproot_and_missing_origin() {
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn::default(), TxIn::default()],
+            output: vec![],
+        };
+        let mut psbt = Psbt::from_unsigned_tx(tx).unwrap();
+        let policy = PathPolicy::from_str("allow m/86'/0'/0'").unwrap();
+        assert_eq!(vec![Verdict::NoKeyOrigin, Verdict::NoKeyOrigin], policy.check_psbt(&psbt).unwrap().verdicts);
+        assert!(!policy.check_psbt(&psbt).unwrap().is_allowed());
+
+        let key = PublicKey::from_str("0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c").unwrap();
+        psbt.inputs[0].tap_key_origins.insert(key.x_only_public_key().0, (vec![], (Fingerprint::default(), DerivationPath::from_str("m/86'/0'/0'/0/0").unwrap())));
+        let report = policy.check_psbt(&psbt).unwrap();
+        assert!(report.verdicts[0].is_allowed());
+        assert_eq!(vec![(1, &Verdict::NoKeyOrigin)], report.rejected().collect::<Vec<_>>());
+
+        psbt.inputs[1].tap_key_origins.insert(key.x_only_public_key().0, (vec![], (Fingerprint::default(), DerivationPath::from_str("m/86'/0'/1'/0/0").unwrap())));
+        let report = policy.check_psbt(&psbt).unwrap();
+        assert_eq!(vec![(1, &Verdict::NotAllowed)], report.rejected().collect::<Vec<_>>());
+    }
+
+    #[test]
+    pub fn empty_psbt_not_allowed() {
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        };
+        let psbt = Psbt::from_unsigned_tx(tx).unwrap();
+        let policy = PathPolicy::from_str("allow m").unwrap();
+        assert!(!policy.check_psbt(&psbt).unwrap().is_allowed());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{classify, AccountHDPath, CustomHDPath, Error, PathKind, ShortHDPath, StandardHDPath};
+use bitcoin::bip32::{Fingerprint, KeySource};
+use bitcoin::psbt::Psbt;
+use bitcoin::secp256k1::{PublicKey, XOnlyPublicKey};
+use bitcoin::taproot::TapLeafHash;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+/// Position of a key in a PSBT
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum PsbtLocation {
+    Input(usize),
+    Output(usize),
+}
+
+/// A public key from a PSBT, either a full key from `bip32_derivation` or an x-only key from
+/// `tap_key_origins`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum PsbtPublicKey {
+    Full(PublicKey),
+    XOnly(XOnlyPublicKey),
+}
+
+impl PsbtPublicKey {
+    /// The key as an x-only key, i.e., as it's used in Taproot
+    pub fn x_only(&self) -> XOnlyPublicKey {
+        match self {
+            PsbtPublicKey::Full(key) => key.x_only_public_key().0,
+            PsbtPublicKey::XOnly(key) => *key,
+        }
+    }
+}
+
+/// A key with its origin (i.e. the master key fingerprint and the HD Path), as it's specified in
+/// the `bip32_derivation` or `tap_key_origins` map of a PSBT input or output.
+///
+/// # Usage
+/// ```
+/// use hdpath::{PsbtKeyPath, StandardHDPath};
+/// use bitcoin::psbt::Psbt;
+///
+/// fn print_paths(psbt: &Psbt) {
+///     for key in PsbtKeyPath::all(psbt).unwrap() {
+///         match key.as_standard() {
+///             Some(hd_path) => println!("{:?}: {}", key.location, hd_path),
+///             None => println!("{:?}: non-standard {}", key.location, key.path)
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PsbtKeyPath {
+    pub location: PsbtLocation,
+    pub public_key: PsbtPublicKey,
+    pub fingerprint: Fingerprint,
+    pub path: CustomHDPath,
+}
+
+impl PsbtKeyPath {
+
+    /// All keys from the `bip32_derivation` and `tap_key_origins` maps of all inputs and outputs of the PSBT.
+    /// Ordered by inputs first, then outputs, and inside each of them by the public key, with
+    /// `bip32_derivation` keys first and then `tap_key_origins` keys.
+    ///
+    /// Fails with `Error::InvalidLength` if any of the paths is longer than 255 elements
+    pub fn all(psbt: &Psbt) -> Result<Vec<PsbtKeyPath>, Error> {
+        let mut result = Vec::new();
+        for (i, input) in psbt.inputs.iter().enumerate() {
+            result.extend(PsbtKeyPath::from_map(PsbtLocation::Input(i), &input.bip32_derivation)?);
+            result.extend(PsbtKeyPath::from_tap_map(PsbtLocation::Input(i), &input.tap_key_origins)?);
+        }
+        for (i, output) in psbt.outputs.iter().enumerate() {
+            result.extend(PsbtKeyPath::from_map(PsbtLocation::Output(i), &output.bip32_derivation)?);
+            result.extend(PsbtKeyPath::from_tap_map(PsbtLocation::Output(i), &output.tap_key_origins)?);
+        }
+        Ok(result)
+    }
+
+    /// All keys from a `bip32_derivation` map
+    pub fn from_map(location: PsbtLocation, map: &BTreeMap<PublicKey, KeySource>) -> Result<Vec<PsbtKeyPath>, Error> {
+        map.iter()
+            .map(|(public_key, (fingerprint, path))| Ok(PsbtKeyPath {
+                location,
+                public_key: PsbtPublicKey::Full(*public_key),
+                fingerprint: *fingerprint,
+                path: CustomHDPath::try_from(path)?,
+            }))
+            .collect()
+    }
+
+    /// All keys from a `tap_key_origins` map
+    pub fn from_tap_map(location: PsbtLocation, map: &BTreeMap<XOnlyPublicKey, (Vec<TapLeafHash>, KeySource)>) -> Result<Vec<PsbtKeyPath>, Error> {
+        map.iter()
+            .map(|(public_key, (_, (fingerprint, path)))| Ok(PsbtKeyPath {
+                location,
+                public_key: PsbtPublicKey::XOnly(*public_key),
+                fingerprint: *fingerprint,
+                path: CustomHDPath::try_from(path)?,
+            }))
+            .collect()
+    }
+
+    /// The path as a standard HD Path (`m/purpose'/coin_type'/account'/change/address_index`) if it has such structure
+    pub fn as_standard(&self) -> Option<StandardHDPath> {
+        StandardHDPath::try_from(self.path.clone()).ok()
+    }
+
+    /// The path as an account HD Path (`m/purpose'/coin_type'/account'`) if it has exactly such structure
+    pub fn as_account(&self) -> Option<AccountHDPath> {
+        if self.path.0.len() != 3 {
+            return None
+        }
+        AccountHDPath::try_from(self.path.clone()).ok()
+    }
+
+    /// The path as a short HD Path (`m/purpose'/coin_type'/account'/index`) if it has such structure
+    pub fn as_short(&self) -> Option<ShortHDPath> {
+        ShortHDPath::try_from(self.path.clone()).ok()
+    }
+
+    /// Kind of the path, see [`classify`](fn.classify.html)
+    pub fn kind(&self) -> PathKind {
+        classify(&self.path)
+    }
+
+    pub fn key_source(&self) -> KeySource {
+        (self.fingerprint, (&self.path).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HDPath;
+    use bitcoin::{absolute, transaction, Amount, OutPoint, ScriptBuf, Transaction, TxIn, TxOut};
+    use bitcoin::bip32::{ChildNumber, DerivationPath};
+    use bitcoin::secp256k1::{Secp256k1, SecretKey};
+    use std::str::FromStr;
+
+    fn key(n: u8) -> PublicKey {
+        PublicKey::from_secret_key(&Secp256k1::new(), &SecretKey::from_slice(&[n; 32]).unwrap())
+    }
+
+    fn psbt() -> Psbt {
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn { previous_output: OutPoint::null(), ..Default::default() }; 2],
+            output: vec![TxOut { value: Amount::from_sat(1000), script_pubkey: ScriptBuf::new() }],
+        };
+        let mut psbt = Psbt::from_unsigned_tx(tx).unwrap();
+        let fingerprint = Fingerprint::from_str("73c5da0a").unwrap();
+        psbt.inputs[0].bip32_derivation.insert(key(1), StandardHDPath::from_str("m/84'/0'/0'/0/5").unwrap().to_key_source(fingerprint));
+        psbt.inputs[1].bip32_derivation.insert(key(2), CustomHDPath::from_str("m/48'/0'/0'/2'/0/1").unwrap().to_key_source(fingerprint));
+        psbt.outputs[0].bip32_derivation.insert(key(3), StandardHDPath::from_str("m/84'/0'/0'/1/2").unwrap().to_key_source(fingerprint));
+        psbt
+    }
+
+    #[test]
+    pub fn get_all_paths() {
+        let act = PsbtKeyPath::all(&psbt()).unwrap();
+        assert_eq!(3, act.len());
+
+        assert_eq!(PsbtLocation::Input(0), act[0].location);
+        assert_eq!(PsbtPublicKey::Full(key(1)), act[0].public_key);
+        assert_eq!(Fingerprint::from_str("73c5da0a").unwrap(), act[0].fingerprint);
+        assert_eq!(Some(StandardHDPath::from_str("m/84'/0'/0'/0/5").unwrap()), act[0].as_standard());
+
+        assert_eq!(PsbtLocation::Input(1), act[1].location);
+        assert_eq!(None, act[1].as_standard());
+        assert_eq!("m/48'/0'/0'/2'/0/1", act[1].path.to_string());
+        assert_eq!(
+            PathKind::Multisig { coin_type: 0, account: 0, script_type: crate::ScriptType::P2wsh, change: 0, index: 1 },
+            act[1].kind()
+        );
+
+        assert_eq!(PsbtLocation::Output(0), act[2].location);
+        assert_eq!(Some(StandardHDPath::from_str("m/84'/0'/0'/1/2").unwrap()), act[2].as_standard());
+    }
+
+    #[test]
+    pub fn as_account() {
+        let fingerprint = Fingerprint::from_str("73c5da0a").unwrap();
+        let mut map = BTreeMap::new();
+        map.insert(key(1), AccountHDPath::from_str("m/84'/0'/3'").unwrap().to_key_source(fingerprint));
+        map.insert(key(2), StandardHDPath::from_str("m/84'/0'/3'/0/0").unwrap().to_key_source(fingerprint));
+        let act = PsbtKeyPath::from_map(PsbtLocation::Input(0), &map).unwrap();
+        let account = act.iter().find(|k| k.public_key == PsbtPublicKey::Full(key(1))).unwrap();
+        assert_eq!(Some(AccountHDPath::from_str("m/84'/0'/3'").unwrap()), account.as_account());
+        assert_eq!(None, account.as_standard());
+        let address = act.iter().find(|k| k.public_key == PsbtPublicKey::Full(key(2))).unwrap();
+        assert_eq!(None, address.as_account());
+    }
+
+    #[test]
+    pub fn as_short() {
+        let fingerprint = Fingerprint::from_str("73c5da0a").unwrap();
+        let mut map = BTreeMap::new();
+        map.insert(key(1), ShortHDPath::from_str("m/44'/60'/0'/7").unwrap().to_key_source(fingerprint));
+        let act = PsbtKeyPath::from_map(PsbtLocation::Output(1), &map).unwrap();
+        assert_eq!(Some(ShortHDPath::from_str("m/44'/60'/0'/7").unwrap()), act[0].as_short());
+        assert_eq!(map.get(&key(1)).unwrap(), &act[0].key_source());
+    }
+
+    #[test]
+    pub fn include_taproot_keys() {
+        let mut psbt = psbt();
+        let fingerprint = Fingerprint::from_str("73c5da0a").unwrap();
+        let x_only = key(4).x_only_public_key().0;
+        psbt.inputs[0].tap_key_origins.insert(x_only, (vec![], StandardHDPath::from_str("m/86'/0'/0'/0/3").unwrap().to_key_source(fingerprint)));
+        psbt.outputs[0].tap_key_origins.insert(key(5).x_only_public_key().0, (vec![], StandardHDPath::from_str("m/86'/0'/0'/1/0").unwrap().to_key_source(fingerprint)));
+        let act = PsbtKeyPath::all(&psbt).unwrap();
+        assert_eq!(5, act.len());
+
+        assert_eq!(PsbtLocation::Input(0), act[1].location);
+        assert_eq!(PsbtPublicKey::XOnly(x_only), act[1].public_key);
+        assert_eq!(x_only, act[1].public_key.x_only());
+        assert_eq!(Some(StandardHDPath::from_str("m/86'/0'/0'/0/3").unwrap()), act[1].as_standard());
+
+        assert_eq!(PsbtLocation::Output(0), act[4].location);
+        assert_eq!(Some(StandardHDPath::from_str("m/86'/0'/0'/1/0").unwrap()), act[4].as_standard());
+    }
+
+    #[test]
+    pub fn err_on_too_long_path() {
+        let mut psbt = psbt();
+        let path = DerivationPath::from(vec![ChildNumber::from_normal_idx(1).unwrap(); 256]);
+        psbt.inputs[1].bip32_derivation.insert(key(4), (Fingerprint::default(), path));
+        assert_eq!(Err(Error::InvalidLength(256)), PsbtKeyPath::all(&psbt));
+    }
+}
@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
 use crate::{PathValue, Error};
 use std::convert::TryFrom;
 #[cfg(feature = "with-bitcoin")]
@@ -6,24 +7,24 @@ use bitcoin::bip32::{ChildNumber};
 
 /// The purpose number, a first number in HD Path, which is supposed to be reference actual format. Supposed to be a hardened value
 /// See [BIP-43](https://github.com/bitcoin/bips/blob/master/bip-0043.mediawiki)
-#[derive(Debug, Clone, Eq, Hash)]
+///
+/// New purposes may be added in future versions, so a `match` on it must have a wildcard arm.
+#[derive(Debug, Clone, Eq)]
+#[non_exhaustive]
 pub enum Purpose {
     None, //0'
     Pubkey, //44'
     ScriptHash, //49'
     Witness, //84'
+    Taproot, //86'
+    Cardano, //1852'
+    Lightning, //1017'
     Custom(u32)
 }
 
 impl PartialOrd for Purpose {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self.as_value().to_raw() > other.as_value().to_raw() {
-            Some(Ordering::Greater)
-        } else if self.as_value().to_raw() == other.as_value().to_raw() {
-            Some(Ordering::Equal)
-        } else {
-            Some(Ordering::Less)
-        }
+        Some(self.cmp(other))
     }
 }
 
@@ -45,27 +46,36 @@ impl PartialEq for Purpose {
     }
 }
 
+impl Hash for Purpose {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // must be consistent with `eq`, i.e. `Custom(44)` is same as `Pubkey`
+        self.as_value().to_raw().hash(state)
+    }
+}
+
 impl Purpose {
-    pub fn as_value(&self) -> PathValue {
+    pub const fn as_value(&self) -> PathValue {
         let n = match self {
             Purpose::None => 0,
             Purpose::Pubkey => 44,
             Purpose::ScriptHash => 49,
             Purpose::Witness => 84,
+            Purpose::Taproot => 86,
+            Purpose::Cardano => 1852,
+            Purpose::Lightning => 1017,
             Purpose::Custom(n) => *n
         };
         PathValue::Hardened(n)
     }
-}
-
-impl TryFrom<u32> for Purpose {
-    type Error = Error;
 
-    fn try_from(value: u32) -> Result<Self, Self::Error> {
+    pub(crate) const fn try_from_number(value: u32) -> Result<Purpose, Error> {
         match value {
             44 => Ok(Purpose::Pubkey),
             49 => Ok(Purpose::ScriptHash),
             84 => Ok(Purpose::Witness),
+            86 => Ok(Purpose::Taproot),
+            1852 => Ok(Purpose::Cardano),
+            1017 => Ok(Purpose::Lightning),
             n => if PathValue::is_ok(n) {
                 Ok(Purpose::Custom(n))
             } else {
@@ -75,6 +85,14 @@ impl TryFrom<u32> for Purpose {
     }
 }
 
+impl TryFrom<u32> for Purpose {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Purpose::try_from_number(value)
+    }
+}
+
 impl From<Purpose> for u32 {
     fn from(value: Purpose) -> Self {
         match value {
@@ -82,7 +100,10 @@ impl From<Purpose> for u32 {
             Purpose::Pubkey => 44,
             Purpose::ScriptHash => 49,
             Purpose::Witness => 84,
-            Purpose::Custom(n) => n.clone()
+            Purpose::Taproot => 86,
+            Purpose::Cardano => 1852,
+            Purpose::Lightning => 1017,
+            Purpose::Custom(n) => n
         }
     }
 }
@@ -94,7 +115,10 @@ impl From<&Purpose> for u32 {
             Purpose::Pubkey => 44,
             Purpose::ScriptHash => 49,
             Purpose::Witness => 84,
-            Purpose::Custom(n) => n.clone()
+            Purpose::Taproot => 86,
+            Purpose::Cardano => 1852,
+            Purpose::Lightning => 1017,
+            Purpose::Custom(n) => *n
         }
     }
 }
@@ -147,12 +171,17 @@ mod tests {
 
     #[test]
     pub fn create_standard_purpose() {
-        assert_eq!(Purpose::Pubkey, Purpose::try_from(44 as u32).unwrap());
-        assert_eq!(Purpose::Pubkey, Purpose::try_from(44 as usize).unwrap());
+        assert_eq!(Purpose::Pubkey, Purpose::try_from(44u32).unwrap());
+        assert_eq!(Purpose::Pubkey, Purpose::try_from(44usize).unwrap());
         assert_eq!(Purpose::Pubkey, Purpose::try_from(44).unwrap());
 
         assert_eq!(Purpose::ScriptHash, Purpose::try_from(49).unwrap());
         assert_eq!(Purpose::Witness, Purpose::try_from(84).unwrap());
+        assert_eq!(Purpose::Taproot, Purpose::try_from(86).unwrap());
+        assert_eq!(Purpose::Cardano, Purpose::try_from(1852).unwrap());
+        assert_eq!(1852, u32::from(Purpose::Cardano));
+        assert_eq!(Purpose::Lightning, Purpose::try_from(1017).unwrap());
+        assert_eq!(1017, u32::from(&Purpose::Lightning));
     }
 
     #[test]
@@ -169,6 +198,7 @@ mod tests {
         assert!(Purpose::Custom(0) < Purpose::Witness);
         assert!(Purpose::Custom(100) > Purpose::Witness);
         assert!(Purpose::Custom(50) > Purpose::Pubkey);
+        assert!(Purpose::Taproot > Purpose::Witness);
     }
 
     #[test]
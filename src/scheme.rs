@@ -0,0 +1,243 @@
+use crate::{CustomHDPath, Error, HDPath, PathValue, Purpose, ShortHDPath, StandardHDPath};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+/// Element of a [`Scheme`](struct.Scheme.html) path, which is either provided by the user or always has the same value
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Slot {
+    Variable,
+    Fixed(u32),
+}
+
+impl Slot {
+    fn resolve(&self, value: u32) -> Result<u32, Error> {
+        match self {
+            Slot::Variable => Ok(value),
+            Slot::Fixed(n) if *n == value => Ok(value),
+            Slot::Fixed(_) => Err(Error::InvalidStructure),
+        }
+    }
+
+    fn accepts(&self, value: u32) -> bool {
+        self.resolve(value).is_ok()
+    }
+}
+
+/// Structure of a [`Scheme`](struct.Scheme.html) path
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Layout {
+    /// `m/purpose'/coin_type'/account'/change/index`, i.e. [`StandardHDPath`](struct.StandardHDPath.html)
+    Standard { change: u32 },
+    /// `m/purpose'/coin_type'/account'/index`, i.e. [`ShortHDPath`](struct.ShortHDPath.html)
+    Short,
+}
+
+/// Encoding of the addresses for the keys of a [`Scheme`](struct.Scheme.html)
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum AddressEncoding {
+    /// Bech32 with the human-readable part, like `cosmos` or `npub`
+    Bech32 { hrp: Cow<'static, str> },
+    /// Hex with EIP-55 checksum
+    Eip55,
+    /// Base58 with checksum
+    Base58Check,
+}
+
+/// Convention of a path used by a wallet or a blockchain, which defines the path structure, which elements of it
+/// vary, and how the addresses are encoded. It can be declared as data, see the presets like `Scheme::COSMOS`.
+///
+/// # Usage
+/// ```
+/// use hdpath::{Scheme, StandardHDPath};
+/// # use std::str::FromStr;
+///
+/// let hd_path = Scheme::COSMOS.path_for(0, 3).unwrap();
+/// assert_eq!("m/44'/118'/0'/0/3", hd_path.to_string());
+///
+/// let hd_path = StandardHDPath::from_str("m/44'/1237'/2'/0/0").unwrap();
+/// assert!(Scheme::NOSTR.matches(&hd_path));
+/// assert_eq!(Some(&Scheme::NOSTR), Scheme::find(&hd_path));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Scheme {
+    pub name: Cow<'static, str>,
+    pub purpose: Purpose,
+    pub coin_type: u32,
+    pub layout: Layout,
+    pub account: Slot,
+    pub index: Slot,
+    pub encoding: AddressEncoding,
+}
+
+impl Scheme {
+
+    /// Cosmos Hub, `m/44'/118'/account'/0/index`
+    pub const COSMOS: Scheme = Scheme::bech32("Cosmos", 118, "cosmos");
+    /// Terra, `m/44'/330'/account'/0/index`
+    pub const TERRA: Scheme = Scheme::bech32("Terra", 330, "terra");
+    /// Secret Network, `m/44'/529'/account'/0/index`
+    pub const SECRET: Scheme = Scheme::bech32("Secret", 529, "secret");
+    /// Nostr keys, `m/44'/1237'/account'/0/0`, see [NIP-06](https://github.com/nostr-protocol/nips/blob/master/06.md)
+    pub const NOSTR: Scheme = Scheme {
+        name: Cow::Borrowed("Nostr"),
+        purpose: Purpose::Pubkey,
+        coin_type: 1237,
+        layout: Layout::Standard { change: 0 },
+        account: Slot::Variable,
+        index: Slot::Fixed(0),
+        encoding: AddressEncoding::Bech32 { hrp: Cow::Borrowed("npub") },
+    };
+    /// Ethereum, `m/44'/60'/0'/0/index` as used by most of the wallets
+    pub const ETHEREUM: Scheme = Scheme {
+        name: Cow::Borrowed("Ethereum"),
+        purpose: Purpose::Pubkey,
+        coin_type: 60,
+        layout: Layout::Standard { change: 0 },
+        account: Slot::Fixed(0),
+        index: Slot::Variable,
+        encoding: AddressEncoding::Eip55,
+    };
+
+    /// All the presets
+    pub const KNOWN: &'static [Scheme] = &[Scheme::COSMOS, Scheme::TERRA, Scheme::SECRET, Scheme::NOSTR, Scheme::ETHEREUM];
+
+    /// Cosmos SDK based chain, `m/44'/coin_type'/account'/0/index` with Bech32 addresses
+    pub const fn bech32(name: &'static str, coin_type: u32, hrp: &'static str) -> Scheme {
+        Scheme {
+            name: Cow::Borrowed(name),
+            purpose: Purpose::Pubkey,
+            coin_type,
+            layout: Layout::Standard { change: 0 },
+            account: Slot::Variable,
+            index: Slot::Variable,
+            encoding: AddressEncoding::Bech32 { hrp: Cow::Borrowed(hrp) },
+        }
+    }
+
+    /// Find the first of the known schemes (see `Scheme::KNOWN`) which matches the path
+    pub fn find(hd_path: &dyn HDPath) -> Option<&'static Scheme> {
+        Scheme::KNOWN.iter().find(|s| s.matches(hd_path))
+    }
+
+    /// Path for the account and index. Returns `Error::InvalidStructure` if the value is different from the fixed value
+    /// of the scheme, or `Error::HighBitIsSet` if it's out of range
+    pub fn path_for(&self, account: u32, index: u32) -> Result<CustomHDPath, Error> {
+        let account = self.account.resolve(account)?;
+        let index = self.index.resolve(index)?;
+        let hd_path = match self.layout {
+            Layout::Standard { change } => {
+                StandardHDPath::try_new(self.purpose.clone(), self.coin_type, account, change, index)
+                    .map_err(|_| Error::HighBitIsSet)?
+                    .as_custom()
+            }
+            Layout::Short => {
+                if !PathValue::is_ok(self.coin_type) || !PathValue::is_ok(account) || !PathValue::is_ok(index) {
+                    return Err(Error::HighBitIsSet)
+                }
+                ShortHDPath { purpose: self.purpose.clone(), coin_type: self.coin_type, account, index }.as_custom()
+            }
+        };
+        Ok(hd_path)
+    }
+
+    /// Account and index of the path, if the path follows the scheme
+    pub fn parse(&self, hd_path: &dyn HDPath) -> Option<(u32, u32)> {
+        let custom = hd_path.as_custom();
+        let (purpose, coin_type, account, index) = match self.layout {
+            Layout::Standard { change } => {
+                let hd_path = StandardHDPath::try_from(custom).ok()?;
+                if hd_path.change() != change {
+                    return None
+                }
+                (hd_path.purpose().clone(), hd_path.coin_type(), hd_path.account(), hd_path.index())
+            }
+            Layout::Short => {
+                let hd_path = ShortHDPath::try_from(custom).ok()?;
+                (hd_path.purpose, hd_path.coin_type, hd_path.account, hd_path.index)
+            }
+        };
+        if purpose != self.purpose || coin_type != self.coin_type
+            || !self.account.accepts(account) || !self.index.accepts(index) {
+            return None
+        }
+        Some((account, index))
+    }
+
+    /// Check if the path follows the scheme
+    pub fn matches(&self, hd_path: &dyn HDPath) -> bool {
+        self.parse(hd_path).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn path(value: &str) -> CustomHDPath {
+        CustomHDPath::from_str(value).unwrap()
+    }
+
+    #[test]
+    pub fn cosmos_paths() {
+        assert_eq!(path("m/44'/118'/0'/0/0"), Scheme::COSMOS.path_for(0, 0).unwrap());
+        assert_eq!(path("m/44'/330'/1'/0/5"), Scheme::TERRA.path_for(1, 5).unwrap());
+        assert_eq!(path("m/44'/529'/0'/0/2"), Scheme::SECRET.path_for(0, 2).unwrap());
+        assert!(Scheme::COSMOS.matches(&path("m/44'/118'/3'/0/7")));
+        assert!(!Scheme::COSMOS.matches(&path("m/44'/118'/3'/1/7")));
+        assert!(!Scheme::COSMOS.matches(&path("m/44'/330'/3'/0/7")));
+        assert!(!Scheme::COSMOS.matches(&path("m/84'/118'/3'/0/7")));
+        assert_eq!(Some((3, 7)), Scheme::COSMOS.parse(&path("m/44'/118'/3'/0/7")));
+    }
+
+    #[test]
+    pub fn nostr_paths() {
+        assert_eq!(path("m/44'/1237'/0'/0/0"), Scheme::NOSTR.path_for(0, 0).unwrap());
+        assert_eq!(path("m/44'/1237'/4'/0/0"), Scheme::NOSTR.path_for(4, 0).unwrap());
+        assert_eq!(Err(Error::InvalidStructure), Scheme::NOSTR.path_for(0, 1));
+        assert!(Scheme::NOSTR.matches(&StandardHDPath::from_str("m/44'/1237'/4'/0/0").unwrap()));
+        assert!(!Scheme::NOSTR.matches(&path("m/44'/1237'/4'/0/1")));
+        assert_eq!(AddressEncoding::Bech32 { hrp: Cow::Borrowed("npub") }, Scheme::NOSTR.encoding);
+    }
+
+    #[test]
+    pub fn ethereum_paths() {
+        assert_eq!(path("m/44'/60'/0'/0/9"), Scheme::ETHEREUM.path_for(0, 9).unwrap());
+        assert_eq!(Err(Error::InvalidStructure), Scheme::ETHEREUM.path_for(1, 0));
+        assert!(!Scheme::ETHEREUM.matches(&path("m/44'/60'/1'/0/0")));
+    }
+
+    #[test]
+    pub fn short_layout() {
+        let scheme = Scheme {
+            name: Cow::Owned("MyEtherWallet Legacy".to_string()),
+            purpose: Purpose::Pubkey,
+            coin_type: 60,
+            layout: Layout::Short,
+            account: Slot::Fixed(0),
+            index: Slot::Variable,
+            encoding: AddressEncoding::Eip55,
+        };
+        assert_eq!(path("m/44'/60'/0'/5"), scheme.path_for(0, 5).unwrap());
+        assert!(scheme.matches(&path("m/44'/60'/0'/5")));
+        assert!(!scheme.matches(&path("m/44'/60'/0'/0/5")));
+        assert_eq!(Err(Error::HighBitIsSet), scheme.path_for(0, 0x80000000));
+    }
+
+    #[test]
+    pub fn err_out_of_range() {
+        assert_eq!(Err(Error::HighBitIsSet), Scheme::COSMOS.path_for(0x80000000, 0));
+        assert_eq!(Err(Error::HighBitIsSet), Scheme::COSMOS.path_for(0, 0x80000000));
+    }
+
+    #[test]
+    pub fn find_known() {
+        assert_eq!(Some(&Scheme::TERRA), Scheme::find(&path("m/44'/330'/0'/0/0")));
+        assert_eq!(Some(&Scheme::ETHEREUM), Scheme::find(&path("m/44'/60'/0'/0/1")));
+        assert_eq!(None, Scheme::find(&path("m/44'/0'/0'/0/0")));
+        assert_eq!(None, Scheme::find(&path("m/44'/118'/0'")));
+        for scheme in Scheme::KNOWN {
+            assert_eq!(Some(scheme), Scheme::find(&scheme.path_for(0, 0).unwrap()));
+        }
+    }
+}
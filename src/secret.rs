@@ -0,0 +1,238 @@
+use crate::{Error, HDPath};
+use bitcoin::NetworkKind;
+use bitcoin::bip32::{ChainCode, ChildNumber, DerivationPath, Fingerprint, Xpriv, Xpub};
+use bitcoin::secp256k1::{PublicKey, Secp256k1, SecretKey, Signing};
+use std::fmt;
+use std::sync::atomic;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// A seed for the master (root) key of an HD wallet.
+///
+/// The value is erased from memory when dropped, compared in constant time, and never printed
+/// with `Debug` or `Display`.
+///
+/// # Usage
+/// ```
+/// use hdpath::{Seed, StandardHDPath};
+/// use bitcoin::NetworkKind;
+/// use bitcoin::secp256k1::Secp256k1;
+/// # use std::str::FromStr;
+///
+/// let secp = Secp256k1::new();
+/// let seed = Seed::from_bytes(&[0u8; 64]).unwrap();
+/// let hd_path = StandardHDPath::from_str("m/84'/0'/0'/0/0").unwrap();
+/// let key = seed.derive_priv(&secp, NetworkKind::Main, &hd_path).unwrap();
+/// ```
+pub struct Seed(Vec<u8>);
+
+impl Seed {
+
+    /// Use the bytes as a seed. BIP-32 requires it to be between 16 and 64 bytes.
+    pub fn from_bytes(value: &[u8]) -> Result<Seed, Error> {
+        if value.len() < 16 || value.len() > 64 {
+            return Err(Error::InvalidLength(value.len()))
+        }
+        Ok(Seed(value.to_vec()))
+    }
+
+    /// Access the actual value of the seed
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Master (root) private key for the seed
+    pub fn to_master(&self, network: NetworkKind) -> Result<ExtendedPrivateKey, Error> {
+        Ok(ExtendedPrivateKey(Xpriv::new_master(network, &self.0)?))
+    }
+
+    /// Derive private key for the specified HD Path
+    pub fn derive_priv<C: Signing>(&self, secp: &Secp256k1<C>, network: NetworkKind, hd_path: &dyn HDPath) -> Result<ExtendedPrivateKey, Error> {
+        self.to_master(network)?.derive(secp, hd_path)
+    }
+}
+
+impl Drop for Seed {
+    fn drop(&mut self) {
+        self.0.zeroize()
+    }
+}
+
+impl PartialEq for Seed {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+}
+
+impl Eq for Seed {}
+
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Seed(<redacted>)")
+    }
+}
+
+impl fmt::Display for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted>")
+    }
+}
+
+/// Extended Private Key, i.e. a private key with a chain code, on some HD Path.
+///
+/// The private key and the chain code are erased from memory when dropped, compared in constant time,
+/// and never printed with `Debug` or `Display`.
+///
+/// Please note that the erasure is a _best effort_. It overwrites only the value owned by this struct,
+/// but the underlying `bitcoin` types are `Copy` and the derivation (in `bitcoin` and `secp256k1`) may leave
+/// copies of the key on the stack or in registers, which are not erased.
+pub struct ExtendedPrivateKey(Xpriv);
+
+impl ExtendedPrivateKey {
+
+    pub fn new(value: Xpriv) -> ExtendedPrivateKey {
+        ExtendedPrivateKey(value)
+    }
+
+    /// Derive a child private key for the relative HD Path
+    pub fn derive<C: Signing>(&self, secp: &Secp256k1<C>, hd_path: &dyn HDPath) -> Result<ExtendedPrivateKey, Error> {
+        Ok(ExtendedPrivateKey(self.0.derive_priv(secp, &DerivationPath::from(hd_path))?))
+    }
+
+    pub fn public_key<C: Signing>(&self, secp: &Secp256k1<C>) -> PublicKey {
+        self.0.private_key.public_key(secp)
+    }
+
+    pub fn to_xpub<C: Signing>(&self, secp: &Secp256k1<C>) -> Xpub {
+        Xpub::from_priv(secp, &self.0)
+    }
+
+    /// Access the actual private key
+    pub fn expose_secret(&self) -> &Xpriv {
+        &self.0
+    }
+}
+
+impl From<Xpriv> for ExtendedPrivateKey {
+    fn from(value: Xpriv) -> Self {
+        ExtendedPrivateKey(value)
+    }
+}
+
+impl Drop for ExtendedPrivateKey {
+    fn drop(&mut self) {
+        // Neither `SecretKey` nor `ChainCode` provide mutable access to their bytes, and `SecretKey::non_secure_erase`
+        // is a plain write which the compiler may remove. So overwrite the whole value with a dummy key the same way
+        // as `zeroize` does, with a volatile write followed by a fence. A zero key is invalid, so it uses `1`.
+        let erased = Xpriv {
+            network: NetworkKind::Main,
+            depth: 0,
+            parent_fingerprint: Fingerprint::default(),
+            child_number: ChildNumber::Normal { index: 0 },
+            private_key: SecretKey::from_slice(&[1u8; 32]).expect("1 is a valid private key"),
+            chain_code: ChainCode::from([0u8; 32]),
+        };
+        // SAFETY: `self.0` is a valid, aligned and exclusively borrowed `Xpriv`, and `Xpriv` is `Copy`, so
+        // overwriting it without dropping the old value doesn't leak or double-free anything.
+        unsafe {
+            std::ptr::write_volatile(&mut self.0, erased);
+        }
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl PartialEq for ExtendedPrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        let mut a = self.0.encode();
+        let mut b = other.0.encode();
+        let result = a.ct_eq(&b).into();
+        a.zeroize();
+        b.zeroize();
+        result
+    }
+}
+
+impl Eq for ExtendedPrivateKey {}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ExtendedPrivateKey(<redacted>)")
+    }
+}
+
+impl fmt::Display for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StandardHDPath;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn seed_length() {
+        assert!(Seed::from_bytes(&[1u8; 16]).is_ok());
+        assert!(Seed::from_bytes(&[1u8; 64]).is_ok());
+        assert_eq!(Err(Error::InvalidLength(15)), Seed::from_bytes(&[1u8; 15]));
+        assert_eq!(Err(Error::InvalidLength(65)), Seed::from_bytes(&[1u8; 65]));
+    }
+
+    #[test]
+    pub fn seed_is_redacted() {
+        let seed = Seed::from_bytes(&[0xabu8; 32]).unwrap();
+        assert_eq!("Seed(<redacted>)", format!("{:?}", seed));
+        assert_eq!("<redacted>", format!("{}", seed));
+        assert!(!format!("{:#?}", seed).contains("ab"));
+    }
+
+    #[test]
+    pub fn key_is_redacted() {
+        let key = Seed::from_bytes(&[0xabu8; 32]).unwrap().to_master(NetworkKind::Main).unwrap();
+        assert_eq!("ExtendedPrivateKey(<redacted>)", format!("{:?}", key));
+        assert_eq!("<redacted>", format!("{}", key));
+    }
+
+    #[test]
+    pub fn compare_seeds() {
+        assert_eq!(Seed::from_bytes(&[1u8; 32]).unwrap(), Seed::from_bytes(&[1u8; 32]).unwrap());
+        assert_ne!(Seed::from_bytes(&[1u8; 32]).unwrap(), Seed::from_bytes(&[2u8; 32]).unwrap());
+        assert_ne!(Seed::from_bytes(&[1u8; 32]).unwrap(), Seed::from_bytes(&[1u8; 33]).unwrap());
+    }
+
+    #[test]
+    pub fn compare_keys() {
+        let secp = Secp256k1::new();
+        let seed = Seed::from_bytes(&[1u8; 32]).unwrap();
+        let hd_path = StandardHDPath::from_str("m/44'/0'/0'/0/0").unwrap();
+        assert_eq!(seed.derive_priv(&secp, NetworkKind::Main, &hd_path).unwrap(), seed.derive_priv(&secp, NetworkKind::Main, &hd_path).unwrap());
+        let other = StandardHDPath::from_str("m/44'/0'/0'/0/1").unwrap();
+        assert_ne!(seed.derive_priv(&secp, NetworkKind::Main, &hd_path).unwrap(), seed.derive_priv(&secp, NetworkKind::Main, &other).unwrap());
+    }
+
+    #[test]
+    pub fn derive_same_as_bitcoin() {
+        let secp = Secp256k1::new();
+        let seed = Seed::from_bytes(&[1u8; 32]).unwrap();
+        let hd_path = StandardHDPath::from_str("m/84'/0'/3'/1/5").unwrap();
+        let exp = Xpriv::new_master(NetworkKind::Main, &[1u8; 32]).unwrap()
+            .derive_priv(&secp, &DerivationPath::from(&hd_path)).unwrap();
+        let act = seed.derive_priv(&secp, NetworkKind::Main, &hd_path).unwrap();
+        assert_eq!(&exp, act.expose_secret());
+        assert_eq!(exp.private_key.public_key(&secp), act.public_key(&secp));
+    }
+
+    #[test]
+    pub fn derive_on_testnet() {
+        let secp = Secp256k1::new();
+        let seed = Seed::from_bytes(&[1u8; 32]).unwrap();
+        let hd_path = StandardHDPath::from_str("m/84'/1'/0'/0/0").unwrap();
+        let main = seed.derive_priv(&secp, NetworkKind::Main, &hd_path).unwrap();
+        let test = seed.derive_priv(&secp, NetworkKind::Test, &hd_path).unwrap();
+        assert_eq!(NetworkKind::Test, test.expose_secret().network);
+        assert!(test.expose_secret().to_string().starts_with("tprv"));
+        assert_eq!(main.public_key(&secp), test.public_key(&secp));
+    }
+}
@@ -0,0 +1,334 @@
+use crate::{CustomHDPath, Error, PathValue};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+#[cfg(feature = "substrate")]
+use blake2::{Blake2b, Digest, digest::consts::U32};
+
+/// Value of a Substrate junction, a number or an arbitrary string
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum JunctionId {
+    Number(u64),
+    Text(String),
+}
+
+/// A single element of a [`SubstrateDerivationPath`](struct.SubstrateDerivationPath.html), same as `PathValue` for BIP-32
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Junction {
+    /// Hard derivation, `//value`
+    Hard(JunctionId),
+    /// Soft derivation, `/value`
+    Soft(JunctionId),
+}
+
+impl Junction {
+
+    pub fn id(&self) -> &JunctionId {
+        match self {
+            Junction::Hard(id) => id,
+            Junction::Soft(id) => id,
+        }
+    }
+
+    pub fn is_hard(&self) -> bool {
+        matches!(self, Junction::Hard(_))
+    }
+
+    /// Chain code used by Substrate for the junction. It's the SCALE encoded value padded to 32 bytes,
+    /// or Blake2b-256 hash of the encoded value if it's longer than 32 bytes.
+    #[cfg(feature = "substrate")]
+    pub fn chain_code(&self) -> [u8; 32] {
+        let encoded = match self.id() {
+            JunctionId::Number(n) => n.to_le_bytes().to_vec(),
+            JunctionId::Text(s) => {
+                let mut buf = compact_len(s.len());
+                buf.extend_from_slice(s.as_bytes());
+                buf
+            }
+        };
+        let mut result = [0u8; 32];
+        if encoded.len() > 32 {
+            result.copy_from_slice(&Blake2b::<U32>::digest(&encoded));
+        } else {
+            result[..encoded.len()].copy_from_slice(&encoded);
+        }
+        result
+    }
+}
+
+/// SCALE compact encoding of a length
+#[cfg(feature = "substrate")]
+fn compact_len(len: usize) -> Vec<u8> {
+    let len = len as u64;
+    if len < 1 << 6 {
+        vec![(len as u8) << 2]
+    } else if len < 1 << 14 {
+        (((len as u16) << 2) | 0b01).to_le_bytes().to_vec()
+    } else if len < 1 << 30 {
+        (((len as u32) << 2) | 0b10).to_le_bytes().to_vec()
+    } else {
+        let bytes = len.to_le_bytes();
+        let size = 8 - bytes.iter().rev().take_while(|b| **b == 0).count();
+        let mut buf = vec![(((size - 4) as u8) << 2) | 0b11];
+        buf.extend_from_slice(&bytes[..size]);
+        buf
+    }
+}
+
+impl fmt::Display for Junction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = if self.is_hard() { "//" } else { "/" };
+        match self.id() {
+            JunctionId::Number(n) => write!(f, "{}{}", prefix, n),
+            JunctionId::Text(s) => write!(f, "{}{}", prefix, s),
+        }
+    }
+}
+
+/// Derivation path used by Substrate (Polkadot, Kusama, etc.) in the format `//hard/soft///password`, for example
+/// `//polkadot//0/1`. A junction can be a number or an arbitrary string. As in Substrate, a junction is a number if it
+/// parses as `u64`, so `/+1` and `/01` are the same as `/1`.
+///
+/// The password is not included in the string produced by `Display` or `Debug`, use `password()` to get it.
+///
+/// # Usage
+/// ```
+/// use hdpath::{CustomHDPath, Junction, JunctionId, SubstrateDerivationPath};
+/// # use std::str::FromStr;
+/// # use std::convert::TryFrom;
+///
+/// let path = SubstrateDerivationPath::from_str("//Alice/1///secret").unwrap();
+/// assert_eq!(&Junction::Hard(JunctionId::Text("Alice".to_string())), path.junctions().first().unwrap());
+/// assert_eq!(Some("secret"), path.password());
+/// assert_eq!("//Alice/1", path.to_string());
+///
+/// let path = SubstrateDerivationPath::from_str("//44//354//0/0").unwrap();
+/// assert_eq!("m/44'/354'/0'/0", CustomHDPath::try_from(&path).unwrap().to_string());
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Default)]
+pub struct SubstrateDerivationPath {
+    junctions: Vec<Junction>,
+    password: Option<String>,
+}
+
+impl SubstrateDerivationPath {
+
+    pub fn new(junctions: Vec<Junction>, password: Option<String>) -> SubstrateDerivationPath {
+        SubstrateDerivationPath { junctions, password }
+    }
+
+    pub fn junctions(&self) -> &[Junction] {
+        &self.junctions
+    }
+
+    pub fn password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+}
+
+impl FromStr for SubstrateDerivationPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mut path, password) = match s.find("///") {
+            Some(pos) => (&s[..pos], Some(s[pos + 3..].to_string())),
+            None => (s, None),
+        };
+        let mut junctions = vec![];
+        while !path.is_empty() {
+            let (hard, rest) = match path.strip_prefix("//") {
+                Some(rest) => (true, rest),
+                None => match path.strip_prefix('/') {
+                    Some(rest) => (false, rest),
+                    None => return Err(Error::InvalidFormat)
+                }
+            };
+            let end = rest.find('/').unwrap_or(rest.len());
+            let value = &rest[..end];
+            if value.is_empty() {
+                return Err(Error::InvalidFormat)
+            }
+            // same rule as Substrate, i.e. anything accepted by `parse::<u64>()`, including `+1` or `007`, is a number
+            let id = match u64::from_str(value) {
+                Ok(n) => JunctionId::Number(n),
+                Err(_) => JunctionId::Text(value.to_string()),
+            };
+            junctions.push(if hard { Junction::Hard(id) } else { Junction::Soft(id) });
+            path = &rest[end..];
+        }
+        Ok(SubstrateDerivationPath { junctions, password })
+    }
+}
+
+impl fmt::Display for SubstrateDerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for junction in &self.junctions {
+            write!(f, "{}", junction)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for SubstrateDerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubstrateDerivationPath")
+            .field("junctions", &self.junctions)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+/// Converts if all junctions are numbers which fit into a BIP-32 value (i.e. less than `2^31`), otherwise returns
+/// `Error::InvalidStructure` or `Error::HighBitIsSet`. The password is ignored.
+impl TryFrom<&SubstrateDerivationPath> for CustomHDPath {
+    type Error = Error;
+
+    fn try_from(value: &SubstrateDerivationPath) -> Result<Self, Self::Error> {
+        let values = value.junctions.iter().map(|j| {
+            let n = match j.id() {
+                JunctionId::Number(n) => u32::try_from(*n).map_err(|_| Error::HighBitIsSet)?,
+                JunctionId::Text(_) => return Err(Error::InvalidStructure),
+            };
+            let value = if j.is_hard() { PathValue::try_hardened(n) } else { PathValue::try_normal(n) };
+            value.map_err(|_| Error::HighBitIsSet)
+        }).collect::<Result<Vec<_>, Error>>()?;
+        CustomHDPath::try_new(values)
+    }
+}
+
+impl From<&CustomHDPath> for SubstrateDerivationPath {
+    fn from(value: &CustomHDPath) -> Self {
+        let junctions = value.0.iter().map(|v| match v {
+            PathValue::Hardened(n) => Junction::Hard(JunctionId::Number(*n as u64)),
+            PathValue::Normal(n) => Junction::Soft(JunctionId::Number(*n as u64)),
+        }).collect();
+        SubstrateDerivationPath { junctions, password: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(value: &str) -> JunctionId {
+        JunctionId::Text(value.to_string())
+    }
+
+    #[test]
+    pub fn parse() {
+        let path = SubstrateDerivationPath::from_str("//polkadot//0/soft/1").unwrap();
+        assert_eq!(&[
+            Junction::Hard(text("polkadot")),
+            Junction::Hard(JunctionId::Number(0)),
+            Junction::Soft(text("soft")),
+            Junction::Soft(JunctionId::Number(1)),
+        ], path.junctions());
+        assert_eq!(None, path.password());
+        assert_eq!("//polkadot//0/soft/1", path.to_string());
+    }
+
+    #[test]
+    pub fn parse_password() {
+        let path = SubstrateDerivationPath::from_str("//Alice///pass/word").unwrap();
+        assert_eq!(&[Junction::Hard(text("Alice"))], path.junctions());
+        assert_eq!(Some("pass/word"), path.password());
+        assert_eq!("//Alice", path.to_string());
+        assert!(!format!("{:?}", path).contains("pass/word"));
+
+        let path = SubstrateDerivationPath::from_str("///password").unwrap();
+        assert!(path.junctions().is_empty());
+        assert_eq!(Some("password"), path.password());
+    }
+
+    #[test]
+    pub fn parse_empty() {
+        let path = SubstrateDerivationPath::from_str("").unwrap();
+        assert_eq!(SubstrateDerivationPath::default(), path);
+    }
+
+    #[test]
+    pub fn parse_numbers() {
+        let path = SubstrateDerivationPath::from_str("/18446744073709551615/18446744073709551616/+1/-1/01/1a").unwrap();
+        assert_eq!(&[
+            Junction::Soft(JunctionId::Number(u64::MAX)),
+            Junction::Soft(text("18446744073709551616")),
+            Junction::Soft(JunctionId::Number(1)),
+            Junction::Soft(text("-1")),
+            Junction::Soft(JunctionId::Number(1)),
+            Junction::Soft(text("1a")),
+        ], path.junctions());
+        assert_eq!("/1", SubstrateDerivationPath::from_str("/+1").unwrap().to_string());
+    }
+
+    #[test]
+    pub fn err_parse_invalid() {
+        assert_eq!(Err(Error::InvalidFormat), SubstrateDerivationPath::from_str("Alice"));
+        assert_eq!(Err(Error::InvalidFormat), SubstrateDerivationPath::from_str("//Alice/"));
+        assert_eq!(Err(Error::InvalidFormat), SubstrateDerivationPath::from_str("/"));
+        assert_eq!(Err(Error::InvalidFormat), SubstrateDerivationPath::from_str("//"));
+    }
+
+    #[test]
+    pub fn convert_to_custom() {
+        let path = SubstrateDerivationPath::from_str("//44//354//0/0/1").unwrap();
+        let custom = CustomHDPath::try_from(&path).unwrap();
+        assert_eq!("m/44'/354'/0'/0/1", custom.to_string());
+        assert_eq!(path, SubstrateDerivationPath::from(&custom));
+    }
+
+    #[test]
+    pub fn err_convert_to_custom() {
+        let path = SubstrateDerivationPath::from_str("//polkadot/0").unwrap();
+        assert_eq!(Err(Error::InvalidStructure), CustomHDPath::try_from(&path));
+        let path = SubstrateDerivationPath::from_str("//2147483648").unwrap();
+        assert_eq!(Err(Error::HighBitIsSet), CustomHDPath::try_from(&path));
+        let path = SubstrateDerivationPath::from_str("//4294967296").unwrap();
+        assert_eq!(Err(Error::HighBitIsSet), CustomHDPath::try_from(&path));
+    }
+}
+
+#[cfg(all(test, feature = "substrate"))]
+mod tests_with_substrate {
+    use super::*;
+
+    fn hex(value: &[u8]) -> String {
+        value.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    pub fn chain_code_text() {
+        let junction = Junction::Hard(JunctionId::Text("Alice".to_string()));
+        let mut expected = [0u8; 32];
+        expected[0] = 5 << 2;
+        expected[1..6].copy_from_slice(b"Alice");
+        assert_eq!(expected, junction.chain_code());
+    }
+
+    #[test]
+    pub fn chain_code_number() {
+        let junction = Junction::Soft(JunctionId::Number(0x0102));
+        let mut expected = [0u8; 32];
+        expected[0] = 0x02;
+        expected[1] = 0x01;
+        assert_eq!(expected, junction.chain_code());
+    }
+
+    #[test]
+    pub fn chain_code_long_text_is_hashed() {
+        let junction = Junction::Hard(JunctionId::Text("a".repeat(40)));
+        assert_eq!("02ac7775ba44703a066694f26190321465637e184af457eefabbdefa677c3d18", hex(&junction.chain_code()));
+        // 31 chars + 1 byte of length fit into 32 bytes
+        let junction = Junction::Hard(JunctionId::Text("a".repeat(31)));
+        assert_eq!(31 << 2, junction.chain_code()[0]);
+    }
+
+    #[test]
+    pub fn compact_length() {
+        assert_eq!(vec![0], compact_len(0));
+        assert_eq!(vec![63 << 2], compact_len(63));
+        assert_eq!(vec![0x01, 0x01], compact_len(64));
+        assert_eq!(vec![0xfd, 0xff], compact_len(16383));
+        assert_eq!(vec![0x02, 0x00, 0x01, 0x00], compact_len(16384));
+        assert_eq!(vec![0x03, 0x00, 0x00, 0x00, 0x40], compact_len(1 << 30));
+    }
+}
@@ -1,7 +1,11 @@
-use crate::{PathValue, CustomHDPath};
+use crate::{PathValue, CustomHDPath, Error};
 use byteorder::{BigEndian, WriteBytesExt};
+use std::convert::TryFrom;
 #[cfg(feature = "with-bitcoin")]
-use bitcoin::bip32::{ChildNumber, DerivationPath};
+use bitcoin::bip32::{ChildNumber, DerivationPath, Fingerprint, KeySource};
+
+/// Max number of path elements accepted by Ledger apps
+pub const LEDGER_MAX_DEPTH: usize = 10;
 
 /// General trait for an HDPath.
 /// Common implementations are [`StandardHDPath`], [`AccountHDPath`] and [`CustomHDPath`]
@@ -9,6 +13,7 @@ use bitcoin::bip32::{ChildNumber, DerivationPath};
 /// [`StandardHDPath`]: struct.StandardHDPath.html
 /// [`AccountHDPath`]: struct.AccountHDPath.html
 /// [`CustomHDPath`]: struct.CustomHDPath.html
+#[allow(clippy::len_without_is_empty)]
 pub trait HDPath {
 
     /// Size of the HD Path
@@ -29,12 +34,78 @@ pub trait HDPath {
         buf.push(len);
         for i in 0..len {
             buf.write_u32::<BigEndian>(self.get(i)
-                .expect(format!("No valut at {}", i).as_str())
+                .unwrap_or_else(|| panic!("No value at {}", i))
                 .to_raw()).unwrap();
         }
         buf
     }
 
+    /// Encode as a path for Ledger APDU, which is one byte with the number of elements following by 4-byte BE values,
+    /// same as `to_bytes`, but limited to [`LEDGER_MAX_DEPTH`](constant.LEDGER_MAX_DEPTH.html) elements.
+    /// Returns `Error::InvalidLength` for a longer path.
+    ///
+    /// ```
+    /// use hdpath::{HDPath, StandardHDPath};
+    /// # use std::str::FromStr;
+    ///
+    /// let hd_path = StandardHDPath::from_str("m/44'/60'/0'/0/1").unwrap();
+    /// assert_eq!(
+    ///     vec![5, 0x80, 0, 0, 44, 0x80, 0, 0, 60, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+    ///     hd_path.to_ledger_apdu().unwrap()
+    /// );
+    /// ```
+    fn to_ledger_apdu(&self) -> Result<Vec<u8>, Error> {
+        let len = self.len() as usize;
+        if len > LEDGER_MAX_DEPTH {
+            return Err(Error::InvalidLength(len))
+        }
+        Ok(self.to_bytes())
+    }
+
+    /// Decode a path from Ledger APDU (see `to_ledger_apdu`). The data must contain only the path.
+    /// Returns `Error::InvalidLength` if the path is longer than [`LEDGER_MAX_DEPTH`](constant.LEDGER_MAX_DEPTH.html),
+    /// `Error::InvalidFormat` if the size of data doesn't match the path length, or an error if it's not a valid path
+    /// of the requested type.
+    fn from_ledger_apdu(data: &[u8]) -> Result<Self, Error>
+        where Self: Sized + TryFrom<CustomHDPath>,
+              Error: From<<Self as TryFrom<CustomHDPath>>::Error> {
+        let len = match data.first() {
+            Some(len) => *len as usize,
+            None => return Err(Error::InvalidFormat)
+        };
+        if len > LEDGER_MAX_DEPTH {
+            return Err(Error::InvalidLength(len))
+        }
+        let hd_path = CustomHDPath::from_bytes(data)?;
+        Ok(Self::try_from(hd_path)?)
+    }
+
+    /// Get raw values of the path (i.e., with `0x80000000` bit for the hardened values), as used for `address_n` field
+    /// of Trezor messages.
+    ///
+    /// ```
+    /// use hdpath::{HDPath, StandardHDPath};
+    /// # use std::str::FromStr;
+    ///
+    /// let hd_path = StandardHDPath::from_str("m/84'/0'/0'/1/5").unwrap();
+    /// assert_eq!(vec![0x80000054, 0x80000000, 0x80000000, 1, 5], hd_path.to_address_n());
+    /// ```
+    fn to_address_n(&self) -> Vec<u32> {
+        (0..self.len())
+            .map(|i| self.get(i).unwrap_or_else(|| panic!("No value at {}", i)).to_raw())
+            .collect()
+    }
+
+    /// Decode a path from Trezor `address_n` values (see `to_address_n`).
+    /// Returns `Error::InvalidLength` if there are more than 255 values, or an error if it's not a valid path
+    /// of the requested type.
+    fn from_address_n(values: &[u32]) -> Result<Self, Error>
+        where Self: Sized + TryFrom<CustomHDPath>,
+              Error: From<<Self as TryFrom<CustomHDPath>>::Error> {
+        let hd_path = CustomHDPath::try_new(values.iter().map(|v| PathValue::from_raw(*v)).collect())?;
+        Ok(Self::try_from(hd_path)?)
+    }
+
     ///
     /// Get parent HD Path.
     /// Return `None` if the current path is empty (i.e. already at the top)
@@ -74,6 +145,14 @@ pub trait HDPath {
         }
         DerivationPath::from(path)
     }
+
+    ///
+    /// Make a key origin for the path (as used in PSBT and descriptors), where `fingerprint` is the
+    /// fingerprint of the master key
+    #[cfg(feature = "with-bitcoin")]
+    fn to_key_source(&self, fingerprint: Fingerprint) -> KeySource {
+        (fingerprint, self.as_bitcoin())
+    }
 }
 
 #[cfg(feature = "with-bitcoin")]
@@ -158,6 +237,128 @@ mod tests {
     }
 }
 
+#[cfg(test)]
+mod tests_hardware {
+    use super::*;
+    use crate::{AccountHDPath, Bip85Path, CardanoHDPath, LightningKeyPath, ShortHDPath, StandardHDPath};
+    use std::str::FromStr;
+
+    #[test]
+    fn ledger_apdu_standard() {
+        let hd_path = StandardHDPath::from_str("m/44'/60'/0'/0/1").unwrap();
+        let expected: Vec<u8> = vec![
+            0x05,
+            0x80, 0x00, 0x00, 0x2c,
+            0x80, 0x00, 0x00, 0x3c,
+            0x80, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x01,
+        ];
+        assert_eq!(expected, hd_path.to_ledger_apdu().unwrap());
+        assert_eq!(hd_path, StandardHDPath::from_ledger_apdu(&expected).unwrap());
+    }
+
+    #[test]
+    fn ledger_apdu_large_values() {
+        let hd_path = CustomHDPath::from_str("m/84'/2147483647'/305419896").unwrap();
+        let expected: Vec<u8> = vec![
+            0x03,
+            0x80, 0x00, 0x00, 0x54,
+            0xff, 0xff, 0xff, 0xff,
+            0x12, 0x34, 0x56, 0x78,
+        ];
+        assert_eq!(expected, hd_path.to_ledger_apdu().unwrap());
+        assert_eq!(hd_path, CustomHDPath::from_ledger_apdu(&expected).unwrap());
+    }
+
+    #[test]
+    fn ledger_apdu_all_types() {
+        let account = AccountHDPath::from_str("m/84'/0'/3'").unwrap();
+        let apdu = account.to_ledger_apdu().unwrap();
+        assert_eq!(vec![0x03, 0x80, 0, 0, 0x54, 0x80, 0, 0, 0, 0x80, 0, 0, 0x03], apdu);
+        assert_eq!(account, AccountHDPath::from_ledger_apdu(&apdu).unwrap());
+
+        let short = ShortHDPath::from_str("m/44'/60'/0'/7").unwrap();
+        let apdu = short.to_ledger_apdu().unwrap();
+        assert_eq!(vec![0x04, 0x80, 0, 0, 0x2c, 0x80, 0, 0, 0x3c, 0x80, 0, 0, 0, 0, 0, 0, 0x07], apdu);
+        assert_eq!(short, ShortHDPath::from_ledger_apdu(&apdu).unwrap());
+
+        let cardano = CardanoHDPath::from_str("m/1852'/1815'/0'/2/0").unwrap();
+        assert_eq!(cardano, CardanoHDPath::from_ledger_apdu(&cardano.to_ledger_apdu().unwrap()).unwrap());
+
+        let lightning = LightningKeyPath::from_str("m/1017'/0'/6'/0/0").unwrap();
+        assert_eq!(lightning, LightningKeyPath::from_ledger_apdu(&lightning.to_ledger_apdu().unwrap()).unwrap());
+
+        let bip85 = Bip85Path::from_str("m/83696968'/2'/0'").unwrap();
+        assert_eq!(bip85, Bip85Path::from_ledger_apdu(&bip85.to_ledger_apdu().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn ledger_apdu_empty() {
+        let hd_path = CustomHDPath(vec![]);
+        assert_eq!(vec![0x00], hd_path.to_ledger_apdu().unwrap());
+        assert_eq!(hd_path, CustomHDPath::from_ledger_apdu(&[0x00]).unwrap());
+    }
+
+    #[test]
+    fn ledger_apdu_max_depth() {
+        let hd_path = CustomHDPath::from_str("m/1/2/3/4/5/6/7/8/9/10").unwrap();
+        let apdu = hd_path.to_ledger_apdu().unwrap();
+        assert_eq!(41, apdu.len());
+        assert_eq!(10, apdu[0]);
+        assert_eq!(hd_path, CustomHDPath::from_ledger_apdu(&apdu).unwrap());
+
+        let hd_path = CustomHDPath::from_str("m/1/2/3/4/5/6/7/8/9/10/11").unwrap();
+        assert_eq!(Err(Error::InvalidLength(11)), hd_path.to_ledger_apdu());
+        assert_eq!(Err(Error::InvalidLength(11)), CustomHDPath::from_ledger_apdu(&hd_path.to_bytes()));
+    }
+
+    #[test]
+    fn err_ledger_apdu_invalid() {
+        assert_eq!(Err(Error::InvalidFormat), CustomHDPath::from_ledger_apdu(&[]));
+        // length doesn't match
+        assert_eq!(Err(Error::InvalidFormat), CustomHDPath::from_ledger_apdu(&[0x02, 0x80, 0, 0, 0x2c]));
+        assert_eq!(Err(Error::InvalidFormat), CustomHDPath::from_ledger_apdu(&[0x01, 0x80, 0, 0, 0x2c, 0x00]));
+        // valid path, but not a standard one
+        let apdu = CustomHDPath::from_str("m/44'/0'/0'").unwrap().to_ledger_apdu().unwrap();
+        assert_eq!(Err(Error::InvalidLength(3)), StandardHDPath::from_ledger_apdu(&apdu));
+    }
+
+    #[test]
+    fn address_n_standard() {
+        let hd_path = StandardHDPath::from_str("m/49'/0'/1'/0/20").unwrap();
+        let expected: Vec<u32> = vec![0x80000031, 0x80000000, 0x80000001, 0, 20];
+        assert_eq!(expected, hd_path.to_address_n());
+        assert_eq!(hd_path, StandardHDPath::from_address_n(&expected).unwrap());
+    }
+
+    #[test]
+    fn address_n_all_types() {
+        let account = AccountHDPath::from_str("m/84'/1'/0'").unwrap();
+        assert_eq!(vec![0x80000054, 0x80000001, 0x80000000], account.to_address_n());
+        assert_eq!(account, AccountHDPath::from_address_n(&account.to_address_n()).unwrap());
+
+        let short = ShortHDPath::from_str("m/44'/60'/0'/7").unwrap();
+        assert_eq!(vec![0x8000002c, 0x8000003c, 0x80000000, 7], short.to_address_n());
+        assert_eq!(short, ShortHDPath::from_address_n(&short.to_address_n()).unwrap());
+
+        let custom = CustomHDPath::from_str("m/0'/1/2147483647'/2147483647").unwrap();
+        assert_eq!(vec![0x80000000, 1, 0xffffffff, 0x7fffffff], custom.to_address_n());
+        assert_eq!(custom, CustomHDPath::from_address_n(&custom.to_address_n()).unwrap());
+
+        let cardano = CardanoHDPath::from_str("m/1852'/1815'/0'/0/3").unwrap();
+        assert_eq!(cardano, CardanoHDPath::from_address_n(&cardano.to_address_n()).unwrap());
+    }
+
+    #[test]
+    fn err_address_n_invalid() {
+        assert_eq!(Err(Error::InvalidLength(256)), CustomHDPath::from_address_n(&[0; 256]));
+        assert_eq!(Err(Error::InvalidStructure), StandardHDPath::from_address_n(&[0x8000002c, 0x80000000, 0x80000000, 0x80000000, 0]));
+        assert_eq!(Err(Error::InvalidLength(4)), StandardHDPath::from_address_n(&[0x8000002c, 0x80000000, 0x80000000, 0]));
+        assert_eq!(0, CustomHDPath::from_address_n(&[]).unwrap().len());
+    }
+}
+
 #[cfg(all(test, feature = "with-bitcoin"))]
 mod tests_with_bitcoin {
     use crate::{StandardHDPath, HDPath};
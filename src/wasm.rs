@@ -0,0 +1,475 @@
+//! JavaScript bindings, enabled with `wasm` feature.
+//!
+//! All errors are thrown as a JS `Error` with `name` set to `"HDPathError"` and an additional `code` property,
+//! which is one of: `HIGH_BIT_IS_SET`, `INVALID_LENGTH`, `INVALID_PURPOSE`, `INVALID_STRUCTURE`, `INVALID_FORMAT`,
+//! `KEY_DERIVATION` or `INVALID_MNEMONIC`.
+//!
+//! ```js
+//! import { StandardHDPath } from "hdpath";
+//!
+//! const hdPath = StandardHDPath.parse("m/84'/0'/0'/0/1");
+//! hdPath.account; // 0
+//! hdPath.toString(); // "m/84'/0'/0'/0/1"
+//! try {
+//!     StandardHDPath.parse("m/84'/0'/0'");
+//! } catch (e) {
+//!     e instanceof Error; // true
+//!     e.name; // "HDPathError"
+//!     e.code; // "INVALID_LENGTH"
+//! }
+//! ```
+
+use crate::{classify, AccountHDPath, CustomHDPath, Error, HDPath, PathKind, Purpose, ScriptType, StandardHDPath};
+use std::convert::TryFrom;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+/// Value of the `code` property of a JS error
+pub(crate) fn error_code(error: &Error) -> &'static str {
+    match error {
+        Error::HighBitIsSet => "HIGH_BIT_IS_SET",
+        Error::InvalidLength(_) => "INVALID_LENGTH",
+        Error::InvalidPurpose(_) => "INVALID_PURPOSE",
+        Error::InvalidStructure => "INVALID_STRUCTURE",
+        Error::InvalidFormat => "INVALID_FORMAT",
+        Error::KeyDerivation => "KEY_DERIVATION",
+        Error::InvalidMnemonic => "INVALID_MNEMONIC",
+    }
+}
+
+/// Error thrown by the functions, a JS `Error` with `name` set to `HDPathError` and a `code` property
+fn to_js_error(error: Error) -> JsValue {
+    let js_error = js_sys::Error::new(&error.to_string());
+    js_error.set_name("HDPathError");
+    let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str("code"), &JsValue::from_str(error_code(&error)));
+    js_error.into()
+}
+
+fn script_type_name(script_type: ScriptType) -> &'static str {
+    match script_type {
+        ScriptType::P2pkh => "p2pkh",
+        ScriptType::P2shP2wpkh => "p2sh-p2wpkh",
+        ScriptType::P2wpkh => "p2wpkh",
+        ScriptType::P2tr => "p2tr",
+        ScriptType::P2shP2wsh => "p2sh-p2wsh",
+        ScriptType::P2wsh => "p2wsh",
+    }
+}
+
+/// Purpose of a path, see [`Purpose`](../enum.Purpose.html)
+#[wasm_bindgen(js_name = Purpose)]
+#[derive(Debug, Clone)]
+pub struct JsPurpose(Purpose);
+
+#[wasm_bindgen(js_class = Purpose)]
+impl JsPurpose {
+    #[wasm_bindgen(constructor)]
+    pub fn new(value: u32) -> Result<JsPurpose, JsValue> {
+        Purpose::try_from(value).map(JsPurpose).map_err(to_js_error)
+    }
+
+    pub fn pubkey() -> JsPurpose {
+        JsPurpose(Purpose::Pubkey)
+    }
+
+    #[wasm_bindgen(js_name = scriptHash)]
+    pub fn script_hash() -> JsPurpose {
+        JsPurpose(Purpose::ScriptHash)
+    }
+
+    pub fn witness() -> JsPurpose {
+        JsPurpose(Purpose::Witness)
+    }
+
+    pub fn taproot() -> JsPurpose {
+        JsPurpose(Purpose::Taproot)
+    }
+
+    /// Number of the purpose, ex. `84`
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> u32 {
+        self.0.as_value().as_number()
+    }
+
+    /// Script type defined by the purpose, ex. `p2wpkh`, or `undefined`
+    #[wasm_bindgen(getter, js_name = scriptType)]
+    pub fn script_type(&self) -> Option<String> {
+        ScriptType::from_purpose(&self.0).map(|t| script_type_name(t).to_string())
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        format!("{}'", self.value())
+    }
+}
+
+/// Result of the path classification, see [`classify`](../fn.classify.html)
+#[wasm_bindgen]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Classification {
+    kind: &'static str,
+    coin_type: Option<u32>,
+    account: Option<u32>,
+    script_type: Option<ScriptType>,
+}
+
+#[wasm_bindgen]
+impl Classification {
+    /// One of `standard`, `account`, `multisig`, `multisigAccount`, `ledgerLive`, `short`, `electrum` or `unknown`
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> String {
+        self.kind.to_string()
+    }
+
+    #[wasm_bindgen(getter, js_name = coinType)]
+    pub fn coin_type(&self) -> Option<u32> {
+        self.coin_type
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn account(&self) -> Option<u32> {
+        self.account
+    }
+
+    /// One of `p2pkh`, `p2sh-p2wpkh`, `p2wpkh`, `p2tr`, `p2sh-p2wsh`, `p2wsh`, or `undefined`
+    #[wasm_bindgen(getter, js_name = scriptType)]
+    pub fn script_type(&self) -> Option<String> {
+        self.script_type.map(|t| script_type_name(t).to_string())
+    }
+}
+
+impl Classification {
+    fn of(hd_path: &dyn HDPath) -> Classification {
+        let kind = classify(hd_path);
+        let name = match kind {
+            PathKind::Standard { .. } => "standard",
+            PathKind::Account { .. } => "account",
+            PathKind::Multisig { .. } => "multisig",
+            PathKind::MultisigAccount { .. } => "multisigAccount",
+            PathKind::LedgerLive { .. } => "ledgerLive",
+            PathKind::Short(_) => "short",
+            PathKind::Electrum { .. } => "electrum",
+            PathKind::Unknown => "unknown",
+        };
+        Classification {
+            kind: name,
+            coin_type: kind.coin_type(),
+            account: kind.account(),
+            script_type: kind.script_type(),
+        }
+    }
+}
+
+/// Standard path `m/purpose'/coin_type'/account'/change/index`, see [`StandardHDPath`](../struct.StandardHDPath.html)
+#[wasm_bindgen(js_name = StandardHDPath)]
+#[derive(Debug, Clone)]
+pub struct JsStandardHDPath(StandardHDPath);
+
+#[wasm_bindgen(js_class = StandardHDPath)]
+impl JsStandardHDPath {
+    #[wasm_bindgen(constructor)]
+    pub fn new(purpose: &JsPurpose, coin_type: u32, account: u32, change: u32, index: u32) -> Result<JsStandardHDPath, JsValue> {
+        StandardHDPath::try_new(purpose.0.clone(), coin_type, account, change, index)
+            .map(JsStandardHDPath)
+            .map_err(|_| to_js_error(Error::HighBitIsSet))
+    }
+
+    pub fn parse(value: &str) -> Result<JsStandardHDPath, JsValue> {
+        StandardHDPath::from_str(value).map(JsStandardHDPath).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn purpose(&self) -> JsPurpose {
+        JsPurpose(self.0.purpose().clone())
+    }
+
+    #[wasm_bindgen(getter, js_name = coinType)]
+    pub fn coin_type(&self) -> u32 {
+        self.0.coin_type()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn account(&self) -> u32 {
+        self.0.account()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn change(&self) -> u32 {
+        self.0.change()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn index(&self) -> u32 {
+        self.0.index()
+    }
+
+    /// Account path of the address
+    #[wasm_bindgen(js_name = accountPath)]
+    pub fn account_path(&self) -> JsAccountHDPath {
+        JsAccountHDPath(AccountHDPath::from(&self.0))
+    }
+
+    pub fn classify(&self) -> Classification {
+        Classification::of(&self.0)
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Account path `m/purpose'/coin_type'/account'`, see [`AccountHDPath`](../struct.AccountHDPath.html)
+#[wasm_bindgen(js_name = AccountHDPath)]
+#[derive(Debug, Clone)]
+pub struct JsAccountHDPath(AccountHDPath);
+
+#[wasm_bindgen(js_class = AccountHDPath)]
+impl JsAccountHDPath {
+    #[wasm_bindgen(constructor)]
+    pub fn new(purpose: &JsPurpose, coin_type: u32, account: u32) -> Result<JsAccountHDPath, JsValue> {
+        AccountHDPath::try_new(purpose.0.clone(), coin_type, account)
+            .map(JsAccountHDPath)
+            .map_err(|_| to_js_error(Error::HighBitIsSet))
+    }
+
+    pub fn parse(value: &str) -> Result<JsAccountHDPath, JsValue> {
+        AccountHDPath::from_str(value).map(JsAccountHDPath).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn purpose(&self) -> JsPurpose {
+        JsPurpose(self.0.purpose().clone())
+    }
+
+    #[wasm_bindgen(getter, js_name = coinType)]
+    pub fn coin_type(&self) -> u32 {
+        self.0.coin_type()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn account(&self) -> u32 {
+        self.0.account()
+    }
+
+    /// Path to the address within the account
+    #[wasm_bindgen(js_name = addressAt)]
+    pub fn address_at(&self, change: u32, index: u32) -> Result<JsStandardHDPath, JsValue> {
+        self.0.address_at(change, index)
+            .map(JsStandardHDPath)
+            .map_err(|_| to_js_error(Error::HighBitIsSet))
+    }
+
+    pub fn classify(&self) -> Classification {
+        Classification::of(&self.0)
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Path of any structure, see [`CustomHDPath`](../struct.CustomHDPath.html)
+#[wasm_bindgen(js_name = CustomHDPath)]
+#[derive(Debug, Clone)]
+pub struct JsCustomHDPath(CustomHDPath);
+
+#[wasm_bindgen(js_class = CustomHDPath)]
+impl JsCustomHDPath {
+    pub fn parse(value: &str) -> Result<JsCustomHDPath, JsValue> {
+        CustomHDPath::from_str(value).map(JsCustomHDPath).map_err(to_js_error)
+    }
+
+    /// Create from raw values, i.e. where hardened values have `0x80000000` bit set
+    #[wasm_bindgen(js_name = fromValues)]
+    pub fn from_values(values: Vec<u32>) -> Result<JsCustomHDPath, JsValue> {
+        CustomHDPath::from_address_n(&values).map(JsCustomHDPath).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(value: &[u8]) -> Result<JsCustomHDPath, JsValue> {
+        CustomHDPath::from_bytes(value).map(JsCustomHDPath).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> u8 {
+        self.0.len()
+    }
+
+    /// Raw values of the path, where hardened values have `0x80000000` bit set
+    pub fn values(&self) -> Vec<u32> {
+        self.0.to_address_n()
+    }
+
+    /// Number at the position (without the hardened bit), or `undefined`
+    pub fn get(&self, pos: u8) -> Option<u32> {
+        self.0.get(pos).map(|v| v.as_number())
+    }
+
+    /// Check if the value at the position is hardened, or `undefined` if there is no such position
+    #[wasm_bindgen(js_name = isHardened)]
+    pub fn is_hardened(&self, pos: u8) -> Option<bool> {
+        self.0.get(pos).map(|v| v.is_hardened())
+    }
+
+    /// Convert to a standard path. Throws an error if it's not a standard path.
+    #[wasm_bindgen(js_name = toStandard)]
+    pub fn to_standard(&self) -> Result<JsStandardHDPath, JsValue> {
+        StandardHDPath::try_from(self.0.clone()).map(JsStandardHDPath).map_err(to_js_error)
+    }
+
+    pub fn classify(&self) -> Classification {
+        Classification::of(&self.0)
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Classify a path string. Throws an error if it's not a valid path.
+#[wasm_bindgen(js_name = classify)]
+pub fn classify_path(value: &str) -> Result<Classification, JsValue> {
+    CustomHDPath::from_str(value)
+        .map(|hd_path| Classification::of(&hd_path))
+        .map_err(to_js_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // JS errors are available only on wasm, see `tests_wasm` for them
+
+    #[test]
+    pub fn standard_accessors() {
+        let hd_path = JsStandardHDPath::new(&JsPurpose::witness(), 0, 1, 0, 5).unwrap();
+        assert_eq!(84, hd_path.purpose().value());
+        assert_eq!(0, hd_path.coin_type());
+        assert_eq!(1, hd_path.account());
+        assert_eq!(0, hd_path.change());
+        assert_eq!(5, hd_path.index());
+        assert_eq!("m/84'/0'/1'/0/5", hd_path.to_js_string());
+        assert_eq!("m/84'/0'/1'/x/x", hd_path.account_path().to_js_string());
+    }
+
+    #[test]
+    pub fn account_address_at() {
+        let account = JsAccountHDPath::new(&JsPurpose::taproot(), 0, 2).unwrap();
+        assert_eq!("m/86'/0'/2'/1/3", account.address_at(1, 3).unwrap().to_js_string());
+        assert_eq!(Some("p2tr".to_string()), account.purpose().script_type());
+    }
+
+    #[test]
+    pub fn custom_values() {
+        let hd_path = JsCustomHDPath::from_values(vec![0x80000030, 1]).unwrap();
+        assert_eq!(2, hd_path.length());
+        assert_eq!(Some(48), hd_path.get(0));
+        assert_eq!(Some(true), hd_path.is_hardened(0));
+        assert_eq!(Some(false), hd_path.is_hardened(1));
+        assert_eq!(None, hd_path.get(2));
+        assert_eq!(vec![0x80000030, 1], hd_path.values());
+        assert_eq!(vec![2, 0x80, 0, 0, 0x30, 0, 0, 0, 1], hd_path.to_bytes());
+    }
+
+    #[test]
+    pub fn classification() {
+        let kind = Classification::of(&CustomHDPath::from_str("m/49'/2'/3'/0/1").unwrap());
+        assert_eq!("standard", kind.kind());
+        assert_eq!(Some(2), kind.coin_type());
+        assert_eq!(Some(3), kind.account());
+        assert_eq!(Some("p2sh-p2wpkh".to_string()), kind.script_type());
+
+        let kind = Classification::of(&CustomHDPath::from_str("m/48'/0'/0'/2'").unwrap());
+        assert_eq!("multisigAccount", kind.kind());
+        assert_eq!(Some("p2wsh".to_string()), kind.script_type());
+
+        let kind = Classification::of(&CustomHDPath::from_str("m/1/0").unwrap());
+        assert_eq!("electrum", kind.kind());
+        assert_eq!(None, kind.coin_type());
+    }
+
+    #[test]
+    pub fn error_codes() {
+        assert_eq!("HIGH_BIT_IS_SET", error_code(&Error::HighBitIsSet));
+        assert_eq!("INVALID_LENGTH", error_code(&Error::InvalidLength(3)));
+        assert_eq!("INVALID_FORMAT", error_code(&Error::InvalidFormat));
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests_wasm {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn error_of(value: JsValue) -> (String, String) {
+        let error = value.dyn_into::<js_sys::Error>().expect("not an Error");
+        let code = js_sys::Reflect::get(&error, &JsValue::from_str("code")).unwrap();
+        (String::from(error.name()), code.as_string().unwrap())
+    }
+
+    #[wasm_bindgen_test]
+    pub fn error_fields() {
+        let error = to_js_error(Error::InvalidLength(3)).dyn_into::<js_sys::Error>().unwrap();
+        assert_eq!("HDPathError", String::from(error.name()));
+        assert_eq!("Invalid length: 3", String::from(error.message()));
+        assert_eq!("HDPathError: Invalid length: 3", String::from(error.to_string()));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn parse_uppercase_hardened() {
+        let hd_path = JsStandardHDPath::parse("M/44H/0H/0H/0/1").unwrap();
+        assert_eq!("m/44'/0'/0'/0/1", hd_path.to_js_string());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn err_parse_overflow() {
+        let err = JsCustomHDPath::parse("m/2147483648").unwrap_err();
+        assert_eq!(("HDPathError".to_string(), "INVALID_FORMAT".to_string()), error_of(err));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn err_parse_not_standard() {
+        let err = JsStandardHDPath::parse("m/84'/0'/0'").unwrap_err();
+        assert_eq!(("HDPathError".to_string(), "INVALID_LENGTH".to_string()), error_of(err));
+        let err = JsCustomHDPath::parse("m/44'/0'/0'/0'/0").unwrap().to_standard().unwrap_err();
+        assert_eq!(("HDPathError".to_string(), "INVALID_STRUCTURE".to_string()), error_of(err));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn err_address_at_hardened() {
+        let account = JsAccountHDPath::parse("m/84'/0'/0'").unwrap();
+        let err = account.address_at(0, 0x80000000).unwrap_err();
+        assert_eq!(("HDPathError".to_string(), "HIGH_BIT_IS_SET".to_string()), error_of(err));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn err_purpose() {
+        let err = JsPurpose::new(0x80000000).unwrap_err();
+        assert_eq!(("HDPathError".to_string(), "HIGH_BIT_IS_SET".to_string()), error_of(err));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn classify_string() {
+        let kind = classify_path("m/44'/60'/3'/0/0").unwrap();
+        assert_eq!("ledgerLive", kind.kind());
+        assert!(classify_path("foo").is_err());
+    }
+}